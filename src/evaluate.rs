@@ -0,0 +1,125 @@
+//! K-fold cross-validation for evaluating predictive quality of the OLS model.
+//! Where `model::train_model` reports in-sample coefficients, this module reports how well
+//! those coefficients generalize to held-out fighters.
+
+use linfa::prelude::*;
+use linfa_linear::LinearRegression;
+use ndarray::Axis;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::error::Error;
+
+use crate::model::{build_design_matrix_subset, drop_columns, zero_variance_columns};
+use crate::preprocess::CleanRecord;
+
+/// Seed for shuffling record indices before splitting into folds, so a given dataset always
+/// produces the same folds (and therefore comparable metrics) across runs.
+const SHUFFLE_SEED: u64 = 42;
+
+/// R^2 and RMSE for a single held-out fold.
+#[derive(Debug, Clone, Copy)]
+pub struct FoldMetrics {
+    pub r2: f64,
+    pub rmse: f64,
+}
+
+/// Cross-validation results: the metrics for every fold plus their mean and standard deviation.
+#[derive(Debug, Clone)]
+pub struct CvSummary {
+    pub folds: Vec<FoldMetrics>,
+    pub r2_mean: f64,
+    pub r2_std: f64,
+    pub rmse_mean: f64,
+    pub rmse_std: f64,
+}
+
+/// Run k-fold cross-validation over `records`: train on k-1 folds, predict `win_rate` on the
+/// held-out fold, and report mean ± standard deviation of R^2 and RMSE across folds.
+///
+/// input: preprocessed records and the number of folds `k`
+/// output: per-fold metrics plus their mean/std, or an error if fewer than `k` records survived
+/// preprocessing
+pub fn cross_validate(records: &[CleanRecord], k: usize) -> Result<CvSummary, Box<dyn Error>> {
+    let n = records.len();
+    if n < k {
+        return Err(format!(
+            "need at least {} records for {}-fold cross-validation, but only {} survived preprocessing",
+            k, k, n
+        ).into());
+    }
+
+    // Shuffle record indices with a fixed seed, then deal them round-robin into k folds.
+    let mut indices: Vec<usize> = (0..n).collect();
+    let mut rng = StdRng::seed_from_u64(SHUFFLE_SEED);
+    indices.shuffle(&mut rng);
+
+    let mut folds: Vec<Vec<usize>> = vec![Vec::new(); k];
+    for (i, &idx) in indices.iter().enumerate() {
+        folds[i % k].push(idx);
+    }
+
+    let mut fold_metrics = Vec::with_capacity(k);
+    for held_out_fold in 0..k {
+        let train_idx: Vec<usize> = folds.iter()
+            .enumerate()
+            .filter(|(f, _)| *f != held_out_fold)
+            .flat_map(|(_, idx)| idx.iter().copied())
+            .collect();
+        let test_idx = &folds[held_out_fold];
+
+        let (x_train, y_train) = build_design_matrix_subset(records, &train_idx);
+        let (x_test, y_test) = build_design_matrix_subset(records, test_idx);
+
+        // A feature with zero variance within this fold's training rows (e.g. `is_imputed` when
+        // none of them needed imputation) makes `x_train` singular; drop it from both the fold's
+        // training and held-out matrices, the same way `model::train_model` does.
+        let drop_cols = zero_variance_columns(&x_train);
+        let (x_train_fit, keep_cols) = drop_columns(&x_train, &drop_cols);
+        let x_test_fit = x_test.select(Axis(1), &keep_cols);
+
+        let dataset = Dataset::new(x_train_fit, y_train);
+        let model = LinearRegression::default().fit(&dataset)?;
+        let predictions = model.predict(&x_test_fit);
+
+        let residuals = &y_test - &predictions;
+        let ss_res: f64 = residuals.iter().map(|e| e * e).sum();
+        let y_mean = y_test.mean().unwrap_or(0.0);
+        let ss_tot: f64 = y_test.iter().map(|v| (v - y_mean).powi(2)).sum();
+
+        let r2 = if ss_tot > 0.0 { 1.0 - ss_res / ss_tot } else { 0.0 };
+        let rmse = (ss_res / test_idx.len() as f64).sqrt();
+
+        fold_metrics.push(FoldMetrics { r2, rmse });
+    }
+
+    let r2s: Vec<f64> = fold_metrics.iter().map(|m| m.r2).collect();
+    let rmses: Vec<f64> = fold_metrics.iter().map(|m| m.rmse).collect();
+
+    Ok(CvSummary {
+        r2_mean: mean(&r2s),
+        r2_std: std_dev(&r2s),
+        rmse_mean: mean(&rmses),
+        rmse_std: std_dev(&rmses),
+        folds: fold_metrics,
+    })
+}
+
+/// Arithmetic mean of a slice, or 0.0 for an empty slice.
+fn mean(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        0.0
+    } else {
+        values.iter().sum::<f64>() / values.len() as f64
+    }
+}
+
+/// Sample standard deviation of a slice (n-1 denominator), or 0.0 when there's fewer than two values.
+fn std_dev(values: &[f64]) -> f64 {
+    if values.len() < 2 {
+        return 0.0;
+    }
+    let m = mean(values);
+    let var = values.iter().map(|v| (v - m).powi(2)).sum::<f64>() / (values.len() - 1) as f64;
+    var.sqrt()
+}
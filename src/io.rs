@@ -1,6 +1,7 @@
 // Module for loading and validating the data. It reads the csv file, validates headers, and handles missing data.
 use std::error::Error;
 use std::fs::File;
+use std::io::{self, Read};
 use csv::{ReaderBuilder, StringRecord};
 use serde::Deserialize;
 use chrono::NaiveDate;
@@ -20,7 +21,11 @@ mod date_format {
 }
 
 /// Matches exactly your 18 CSV columns.
+///
+/// `significant_striking_accuracy` and `significant_strike_defence` round out the schema but
+/// aren't read by any engineered feature yet; kept so the struct still mirrors every CSV column.
 #[derive(Debug, Deserialize)]
+#[allow(dead_code)]
 pub struct FighterRecord {
     #[serde(rename = "name")]   pub name: String,
     #[serde(rename = "nickname")]   pub nickname: Option<String>,
@@ -49,13 +54,24 @@ pub struct FighterRecord {
                                       pub average_submissions_attempted_per_15_minutes: Option<f32>,
 }
 
+/// Load fighter records from `path`, or from stdin if `path` is `"-"`.
+/// input: filesystem path to the fighters CSV, or `"-"` for stdin
+/// output: parsed, validated `FighterRecord`s
 pub fn load_csv(path: &str) -> Result<Vec<FighterRecord>, Box<dyn Error>> {
-    let file = File::open(path)?;
+    if path == "-" {
+        parse_csv(io::stdin())
+    } else {
+        parse_csv(File::open(path)?)
+    }
+}
+
+/// Parse fighter records out of any `Read` source, validating headers and skipping malformed rows.
+fn parse_csv<R: Read>(reader: R) -> Result<Vec<FighterRecord>, Box<dyn Error>> {
     let mut rdr = ReaderBuilder::new()
         .delimiter(b',')
         .flexible(true)
         .has_headers(true)
-        .from_reader(file);
+        .from_reader(reader);
 
     // Grab and own the header row
     let headers = rdr.headers()?.clone();
@@ -5,27 +5,30 @@ use std::env;
 mod io;
 mod preprocess;
 mod model;
+mod evaluate;
 
 use io::load_csv;
-use preprocess::preprocess;
-use model::train_model;
+use preprocess::{preprocess, ImputationStrategy, NormalizationStrategy};
+use model::{train_model, train_ranker, CoefficientEstimate, Regularization};
+use evaluate::cross_validate;
 use plotters::prelude::*;
 
-/// Draws a horizontal bar chart of feature importances and saves it as “feature_importances.png”
-/// input: feature names with their coefficients  
+/// Draws a horizontal bar chart of feature importances, with 95% CI whiskers, and saves it as
+/// “feature_importances.png”
+/// input: fitted coefficients with their standard errors and confidence intervals
 /// output: none (saves "feature_importances.png" to the current directory)
-/// logic: split "results" into names and values; compute X‐axis range; set up PNG backend; 
-/// build Cartesian chart; label Y ticks with feature names; draw one bar per coefficient  
-fn plot_importances(results: &[(String, f64)]) -> Result<(), Box<dyn std::error::Error>> {
+/// logic: split "results" into names and values; compute X‐axis range from the CI bounds;
+/// set up PNG backend; build Cartesian chart; label Y ticks with feature names; draw one bar
+/// per coefficient, then a whisker spanning [ci_low, ci_high] for each
+fn plot_importances(results: &[CoefficientEstimate]) -> Result<(), Box<dyn std::error::Error>> {
     // Split into names and coefficients
-    let names: Vec<&str> = results.iter().map(|(n, _)| n.as_str()).collect();
-    let coefs: Vec<f64> = results.iter().map(|(_, c)| *c).collect();
+    let names: Vec<&str> = results.iter().map(|r| r.name.as_str()).collect();
     // Use the total number of results for the Y-axis range
     let count = results.len();
 
-    // Determine X axis range with padding
-    let min_x = coefs.iter().cloned().fold(f64::INFINITY, f64::min);
-    let max_x = coefs.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    // Determine X axis range with padding, wide enough to fit every confidence interval
+    let min_x = results.iter().map(|r| r.ci_low).fold(f64::INFINITY, f64::min);
+    let max_x = results.iter().map(|r| r.ci_high).fold(f64::NEG_INFINITY, f64::max);
     let pad = (max_x - min_x) * 0.1;
     let x_range = (min_x - pad)..(max_x + pad);
 
@@ -49,7 +52,7 @@ fn plot_importances(results: &[(String, f64)]) -> Result<(), Box<dyn std::error:
         // Show one label per feature; hide any out‑of‑range ticks
         .y_labels(count)
         .y_label_formatter(&|idx| {
-            let i = *idx as usize;
+            let i = *idx;
             if i < count {
                 names[i].to_string()
             } else {
@@ -62,44 +65,204 @@ fn plot_importances(results: &[(String, f64)]) -> Result<(), Box<dyn std::error:
 
     // Draw horizontal bars for each feature index 0..count
     chart.draw_series(
-        coefs.iter().enumerate().map(|(i, &coef)| {
-            let start = 0.0_f64.min(coef);
-            let end = 0.0_f64.max(coef);
+        results.iter().enumerate().map(|(i, r)| {
+            let start = 0.0_f64.min(r.coefficient);
+            let end = 0.0_f64.max(r.coefficient);
             Rectangle::new([(start, i), (end, i + 1)], BLUE.mix(0.5).filled())
         })
     )?;
 
+    // Draw a 95% CI whisker along the top edge of each bar's row
+    chart.draw_series(
+        results.iter().enumerate().map(|(i, r)| {
+            PathElement::new(vec![(r.ci_low, i), (r.ci_high, i)], BLACK.stroke_width(2))
+        })
+    )?;
+
+    Ok(())
+}
+
+/// Parse an `--impute` flag value into an `ImputationStrategy`.
+/// Accepts "drop", "mean", "median", or "constant:<value>" (e.g. "constant:0.0").
+fn parse_impute_strategy(value: &str) -> Result<ImputationStrategy, Box<dyn Error>> {
+    match value.split_once(':') {
+        Some(("constant", v)) => Ok(ImputationStrategy::Constant(v.parse()?)),
+        None if value == "drop" => Ok(ImputationStrategy::Drop),
+        None if value == "mean" => Ok(ImputationStrategy::Mean),
+        None if value == "median" => Ok(ImputationStrategy::Median),
+        _ => Err(format!("unknown --impute strategy '{}' (expected drop, mean, median, or constant:<value>)", value).into()),
+    }
+}
+
+/// Parse a `--normalize` flag value into a `NormalizationStrategy`.
+fn parse_normalization_strategy(value: &str) -> Result<NormalizationStrategy, Box<dyn Error>> {
+    match value {
+        "minmax" => Ok(NormalizationStrategy::MinMax),
+        "zscore" => Ok(NormalizationStrategy::ZScore),
+        "robust" => Ok(NormalizationStrategy::Robust),
+        _ => Err(format!("unknown --normalize strategy '{}' (expected minmax, zscore, or robust)", value).into()),
+    }
+}
+
+/// Which training objective `main` should fit: ordinary least squares against raw `win_rate`,
+/// or the pairwise ranking objective from `model::train_ranker`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Objective {
+    Ols,
+    Rank,
+}
+
+/// Parse an `--objective` flag value into an `Objective`.
+fn parse_objective(value: &str) -> Result<Objective, Box<dyn Error>> {
+    match value {
+        "ols" => Ok(Objective::Ols),
+        "rank" => Ok(Objective::Rank),
+        _ => Err(format!("unknown --objective '{}' (expected ols or rank)", value).into()),
+    }
+}
+
+/// Parse a `--regularization` flag value into a `Regularization`.
+/// Accepts "none", "ridge:<lambda>", or "elasticnet:<lambda>" (e.g. "ridge:0.1").
+fn parse_regularization(value: &str) -> Result<Regularization, Box<dyn Error>> {
+    match value.split_once(':') {
+        Some(("ridge", v)) => Ok(Regularization::Ridge(v.parse()?)),
+        Some(("elasticnet", v)) => Ok(Regularization::ElasticNet(v.parse()?)),
+        None if value == "none" => Ok(Regularization::None),
+        _ => Err(format!(
+            "unknown --regularization '{}' (expected none, ridge:<lambda>, or elasticnet:<lambda>)",
+            value
+        ).into()),
+    }
+}
+
+/// How `main` should emit the fitted coefficients: an aligned human-readable table, a
+/// `feature,coefficient` CSV stream, or a full JSON serialization (including standard errors
+/// and confidence intervals).
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum OutputFormat {
+    Table,
+    Csv,
+    Json,
+}
+
+/// Parse an `--output` flag value into an `OutputFormat`.
+fn parse_output_format(value: &str) -> Result<OutputFormat, Box<dyn Error>> {
+    match value {
+        "table" => Ok(OutputFormat::Table),
+        "csv" => Ok(OutputFormat::Csv),
+        "json" => Ok(OutputFormat::Json),
+        _ => Err(format!("unknown --output format '{}' (expected table, csv, or json)", value).into()),
+    }
+}
+
+/// Print the fitted coefficients to stdout in the requested format.
+/// input: the fitted coefficients and the format to render them in
+/// output: none (writes to stdout)
+fn print_results(results: &[CoefficientEstimate], format: OutputFormat) -> Result<(), Box<dyn Error>> {
+    match format {
+        OutputFormat::Table => {
+            println!("\nFeature importances (standardized, with 95% confidence intervals):");
+            for r in results {
+                println!(
+                    "{:<30} {:>8.4}  raw={:>8.4}  SE={:>7.4}  CI=[{:>8.4}, {:>8.4}]",
+                    r.name, r.coefficient, r.raw_coefficient, r.std_error, r.ci_low, r.ci_high
+                );
+            }
+        }
+        OutputFormat::Csv => {
+            println!("feature,coefficient");
+            for r in results {
+                println!("{},{}", r.name, r.coefficient);
+            }
+        }
+        OutputFormat::Json => {
+            println!("{}", serde_json::to_string(results)?);
+        }
+    }
     Ok(())
 }
 
-/// load data, preprocess, model training, and visualize
+/// load data, preprocess, optionally cross-validate, model training, and visualize
 /// input: none
 /// output: none
-/// logic: Parse CLI argument for CSV path; Call "load_csv"; Call "preprocess(&raw)";
-/// Call "train_model(&cleaned)";Print each "(feature, coefficient) to stdout;
-/// Call “plot_importances(&results)” to save a bar‑chart PNG  
+/// logic: Parse CLI arguments for the CSV path (or "-" for stdin), an optional "--cv K" fold
+/// count, an optional "--impute" strategy, an optional "--normalize" strategy, an optional
+/// "--output" format, an optional "--objective" (ols or rank), and an optional
+/// "--regularization"; Call "load_csv"; Call "preprocess(&raw, impute_strategy,
+/// normalization_strategy)"; if "--cv" was given, run "cross_validate" and print the mean ±
+/// std R^2/RMSE; Call "train_model" or "train_ranker" depending on "--objective"; Call
+/// "print_results" to render the coefficients in the requested format; Call
+/// “plot_importances(&results)” to save a bar‑chart PNG
 fn main() -> Result<(), Box<dyn Error>> {
-    // 1) Read CSV path
-    let path = env::args()
-        .nth(1)
-        .unwrap_or_else(|| "ufc-fighters-statistics.csv".into());
-    println!("Loading data from {}...", path);
+    // 1) Parse CLI args: an optional positional CSV path ("-" for stdin), "--cv K",
+    // "--impute STRATEGY", "--normalize {minmax,zscore,robust}", "--output {table,csv,json}",
+    // "--objective {ols,rank}", and "--regularization {none,ridge:<lambda>,elasticnet:<lambda>}"
+    let mut path = "ufc-fighters-statistics.csv".to_string();
+    let mut cv_folds: Option<usize> = None;
+    let mut impute_strategy = ImputationStrategy::Mean;
+    let mut normalization_strategy = NormalizationStrategy::MinMax;
+    let mut output_format = OutputFormat::Table;
+    let mut objective = Objective::Ols;
+    let mut regularization = Regularization::None;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--cv" => {
+                let value = args.next().ok_or("--cv requires a fold count, e.g. --cv 5")?;
+                cv_folds = Some(value.parse()?);
+            }
+            "--impute" => {
+                let value = args.next().ok_or("--impute requires a strategy, e.g. --impute median")?;
+                impute_strategy = parse_impute_strategy(&value)?;
+            }
+            "--normalize" => {
+                let value = args.next().ok_or("--normalize requires a strategy, e.g. --normalize zscore")?;
+                normalization_strategy = parse_normalization_strategy(&value)?;
+            }
+            "--output" => {
+                let value = args.next().ok_or("--output requires a format, e.g. --output json")?;
+                output_format = parse_output_format(&value)?;
+            }
+            "--objective" => {
+                let value = args.next().ok_or("--objective requires a value, e.g. --objective rank")?;
+                objective = parse_objective(&value)?;
+            }
+            "--regularization" => {
+                let value = args.next().ok_or("--regularization requires a value, e.g. --regularization ridge:0.1")?;
+                regularization = parse_regularization(&value)?;
+            }
+            other => path = other.to_string(),
+        }
+    }
+    eprintln!("Loading data from {}...", path);
 
     // 2) Load and preprocess
     let raw = load_csv(&path)?;
-    let cleaned = preprocess(&raw);
-    println!("Processed {} records", cleaned.len());
+    let cleaned = preprocess(&raw, impute_strategy, normalization_strategy);
+    eprintln!("Processed {} records", cleaned.len());
 
-    // 3) Train model
-    let results = train_model(&cleaned)?;
-    println!("\nFeature importances:");
-    for (name, coef) in &results {
-        println!("{:<30} {:>8.4}", name, coef);
+    // 2.5) Optional k-fold cross-validation (always evaluates the OLS objective)
+    if let Some(k) = cv_folds {
+        let summary = cross_validate(&cleaned, k)?;
+        eprintln!("\n{}-fold cross-validation:", k);
+        for (i, fold) in summary.folds.iter().enumerate() {
+            eprintln!("  fold {}: R^2 = {:.4}, RMSE = {:.4}", i + 1, fold.r2, fold.rmse);
+        }
+        eprintln!("  R^2:  {:.4} ± {:.4}", summary.r2_mean, summary.r2_std);
+        eprintln!("  RMSE: {:.4} ± {:.4}", summary.rmse_mean, summary.rmse_std);
     }
 
+    // 3) Train model with the chosen objective
+    let results = match objective {
+        Objective::Ols => train_model(&cleaned, regularization)?,
+        Objective::Rank => train_ranker(&cleaned)?,
+    };
+    print_results(&results, output_format)?;
+
     // 4) Plot and save to PNG
     plot_importances(&results)?;
-    println!("Wrote feature_importances.png");
+    eprintln!("Wrote feature_importances.png");
 
     Ok(())
 }
@@ -107,7 +270,6 @@ fn main() -> Result<(), Box<dyn Error>> {
 /// the test functions
 #[cfg(test)]
 mod tests {
-    use super::*;
     use std::{fs::File, io::Write};
     use std::error::Error;
     use chrono::NaiveDate;
@@ -115,15 +277,46 @@ mod tests {
     // for IO tests
     use crate::io::load_csv;
     // for preprocessing tests
-    use crate::preprocess::{preprocess, CleanRecord, Stance, WeightClass};
+    use crate::preprocess::{preprocess, CleanRecord, ImputationStrategy, NormalizationStrategy, Stance, WeightClass};
     // for model tests
-    use crate::model::train_model;
+    use crate::model::{train_model, train_ranker, Regularization};
+    // for cross-validation tests
+    use crate::evaluate::cross_validate;
+    // for feature-scaler reuse tests
+    use crate::preprocess::preprocess_with_scaler;
+    // for weight-class resampling tests
+    use crate::preprocess::resample_balanced;
+    use std::collections::HashMap;
+    // for the load+preprocess convenience wrapper
+    use crate::preprocess::make_weight_driven_data;
+
+    /// A test fixture CSV written under the OS temp dir instead of the repo root, and removed
+    /// automatically when it drops, so `cargo test` never leaves stray files in the working tree.
+    struct TempCsv(std::path::PathBuf);
+
+    impl TempCsv {
+        fn create(name: &str) -> Result<(Self, File), Box<dyn Error>> {
+            let path = std::env::temp_dir().join(name);
+            let f = File::create(&path)?;
+            Ok((TempCsv(path), f))
+        }
+
+        fn as_str(&self) -> &str {
+            self.0.to_str().unwrap()
+        }
+    }
+
+    impl Drop for TempCsv {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_file(&self.0);
+        }
+    }
 
     /// IO: can read a single well‑formed record
     #[test]
     fn test_load_csv() -> Result<(), Box<dyn Error>> {
-        let path = "test_fighters.csv";
-        let mut f = File::create(path)?;
+        let (fixture, mut f) = TempCsv::create("test_fighters.csv")?;
+        let path = fixture.as_str();
         writeln!(&mut f, concat!(
             "name,nickname,wins,losses,draws,",
             "height_cm,weight_in_kg,reach_in_cm,stance,",
@@ -153,16 +346,16 @@ mod tests {
         assert_eq!(r.wins, 10);
         assert!((r.height_cm.unwrap() - 180.0).abs() < 1e-6);
         assert_eq!(r.stance, "Orthodox");
-        assert_eq!(r.date_of_birth, NaiveDate::from_ymd(1990,1,1));
+        assert_eq!(r.date_of_birth, NaiveDate::from_ymd_opt(1990,1,1).unwrap());
         Ok(())
     }
 
-    /// PREPROCESS: drops bad rows, computes one‑hots + ratios + per‑minute
-    /// PREPROCESS: drops bad rows and normalizes all numeric features to 0.0
+    /// PREPROCESS: drops invalid rows, computes one‑hots + ratios + per‑minute,
+    /// and normalizes all numeric features to [0,1]
     #[test]
     fn test_preprocess_filters_and_features() -> Result<(), Box<dyn Error>> {
-        let path = "test_pre.csv";
-        let mut f = File::create(path)?;
+        let (fixture, mut f) = TempCsv::create("test_pre.csv")?;
+        let path = fixture.as_str();
         writeln!(&mut f, concat!(
             "name,nickname,wins,losses,draws,",
             "height_cm,weight_in_kg,reach_in_cm,stance,",
@@ -180,61 +373,702 @@ mod tests {
             "A,,10,2,1,180.0,90.0,190.0,Orthodox,1990-01-01,",
             "5.0,0.5,3.0,0.6,30.0,0.4,0.7,15.0"
         ))?;
-        // bad row (missing weight)
+        // row with a missing weight, which should now be imputed rather than dropped
         writeln!(&mut f, concat!(
             "B,,8,3,1,180.0,,190.0,Southpaw,1992-06-01,",
             "4.0,0.4,2.0,0.5,20.0,0.3,0.6,10.0"
         ))?;
-    
+        // invalid row (non-positive height) is still dropped outright
+        writeln!(&mut f, concat!(
+            "C,,5,5,0,0.0,80.0,190.0,Orthodox,1991-01-01,",
+            "3.0,0.3,2.5,0.5,10.0,0.2,0.5,5.0"
+        ))?;
+
         let raw     = load_csv(path)?;
-        let cleaned = preprocess(&raw);
-        assert_eq!(cleaned.len(), 1);
-        let cr = &cleaned[0];
-    
-        // Stance one‑hots should still be correct
-        assert_eq!(cr.is_orthodox, 1.0);
-        assert_eq!(cr.is_southpaw, 0.0);
-    
-        // Because only one record survived, **all** normalized numeric features == 0.0
-        assert_eq!(cr.weight_height_ratio,     0.0);
-        assert_eq!(cr.reach_height_ratio,      0.0);
-        assert_eq!(cr.submission_per_takedown, 0.0);
-        assert_eq!(cr.takedown_lpm,            0.0);
-        assert_eq!(cr.submission_lpm,          0.0);
-    
+        let cleaned = preprocess(&raw, ImputationStrategy::Mean, NormalizationStrategy::MinMax);
+        assert_eq!(cleaned.len(), 2);
+        let a = cleaned.iter().find(|r| r.is_orthodox == 1.0 && r.is_southpaw == 0.0).unwrap();
+        let b = cleaned.iter().find(|r| r.is_southpaw == 1.0).unwrap();
+
+        // A had every field present
+        assert_eq!(a.is_imputed, 0.0);
+        // B's missing weight was filled in rather than dropping the row
+        assert_eq!(b.is_imputed, 1.0);
+
+        // The mean weight fill is learned only from rows that survive cleaning (A and B; C is
+        // dropped outright for its non-positive height), and A is the only one of those with a
+        // present weight, so B's fill equals A's weight exactly. That makes weight_height_ratio
+        // identical for both records, so it normalizes to 0.0 with zero min-max spread.
+        assert_eq!(a.weight_height_ratio, 0.0);
+        assert_eq!(b.weight_height_ratio, 0.0);
+
         Ok(())
     }
-    
 
-    /// MODEL: simplest two‑point dataset: weight_height_ratio maps to win_rate
+
+    /// MODEL: varied dataset (enough rows to estimate standard errors) where
+    /// weight_height_ratio tracks win_rate closely, `is_imputed` has both values represented, and
+    /// every other numeric feature is the fractional part of `i` times a distinct irrational-ish
+    /// multiplier — unlike a `i % k` cycle, this can't land in the span of the stance/weight-class
+    /// one-hots (whose own cycles are period 3 and period 8), so `X^T X` stays invertible.
     #[test]
     fn test_train_model_simple() {
-        let recs = vec![
+        let stances = [Stance::Orthodox, Stance::Southpaw, Stance::Switch];
+        let classes = [
+            WeightClass::Flyweight, WeightClass::Bantamweight, WeightClass::Featherweight,
+            WeightClass::Lightweight, WeightClass::Welterweight, WeightClass::Middleweight,
+            WeightClass::LightHeavyweight, WeightClass::Heavyweight,
+        ];
+        let multipliers = [0.414_f32, 0.732, 0.236, 0.890, 0.577, 0.347, 0.123, 0.951, 0.271];
+        let frac = |i: usize, m: f32| (i as f32 * m).fract();
+
+        let recs: Vec<CleanRecord> = (0..30).map(|i| {
+            let stance = stances[i % stances.len()];
+            let (is_orthodox, is_southpaw, is_switch) = match stance {
+                Stance::Orthodox => (1.0, 0.0, 0.0),
+                Stance::Southpaw => (0.0, 1.0, 0.0),
+                Stance::Switch   => (0.0, 0.0, 1.0),
+            };
+            let t = i as f32 / 29.0;
+            CleanRecord {
+                stance,
+                is_orthodox, is_southpaw, is_switch,
+                weight_height_ratio: t,
+                reach_height_ratio: frac(i, multipliers[0]),
+                submission_per_takedown: frac(i, multipliers[1]),
+                weight_class: classes[i % classes.len()],
+                age: frac(i, multipliers[2]),
+                significant_strikes_lpm: frac(i, multipliers[3]),
+                strike_diff: frac(i, multipliers[4]),
+                takedown_lpm: frac(i, multipliers[5]),
+                submission_lpm: frac(i, multipliers[6]),
+                takedown_accuracy: frac(i, multipliers[7]),
+                takedown_defense: frac(i, multipliers[8]),
+                win_rate: t,
+                // Imputed on a period-5 cycle: mixes both values without aliasing the
+                // period-3 stance / period-8 weight-class cycles above.
+                is_imputed: if i % 5 < 2 { 1.0 } else { 0.0 },
+            }
+        }).collect();
+
+        let coefs = train_model(&recs, Regularization::None).expect("training failed");
+        let wh = coefs.iter()
+            .find(|c| c.name == "weight_height_ratio")
+            .unwrap();
+        assert!(wh.coefficient > 0.0, "Expected positive coefficient for weight_height_ratio");
+        assert!(wh.std_error >= 0.0, "Standard error should be non-negative");
+        assert!(wh.ci_low <= wh.coefficient && wh.coefficient <= wh.ci_high,
+            "Coefficient should lie within its own confidence interval");
+    }
+
+    /// MODEL: `Ridge`/`ElasticNet` take a different fit path than `None` (penalized normal
+    /// equations / `linfa_elasticnet::ElasticNet` instead of plain `LinearRegression`), with
+    /// SE/CI reported as zero since they aren't well-defined in closed form for a penalized fit.
+    #[test]
+    fn test_train_model_supports_ridge_and_elasticnet_regularization() {
+        let stances = [Stance::Orthodox, Stance::Southpaw, Stance::Switch];
+        let classes = [
+            WeightClass::Flyweight, WeightClass::Bantamweight, WeightClass::Featherweight,
+            WeightClass::Lightweight, WeightClass::Welterweight, WeightClass::Middleweight,
+            WeightClass::LightHeavyweight, WeightClass::Heavyweight,
+        ];
+        let multipliers = [0.414_f32, 0.732, 0.236, 0.890, 0.577, 0.347, 0.123, 0.951, 0.271];
+        let frac = |i: usize, m: f32| (i as f32 * m).fract();
+
+        let recs: Vec<CleanRecord> = (0..30).map(|i| {
+            let stance = stances[i % stances.len()];
+            let (is_orthodox, is_southpaw, is_switch) = match stance {
+                Stance::Orthodox => (1.0, 0.0, 0.0),
+                Stance::Southpaw => (0.0, 1.0, 0.0),
+                Stance::Switch   => (0.0, 0.0, 1.0),
+            };
+            let t = i as f32 / 29.0;
+            CleanRecord {
+                stance,
+                is_orthodox, is_southpaw, is_switch,
+                weight_height_ratio: t,
+                reach_height_ratio: frac(i, multipliers[0]),
+                submission_per_takedown: frac(i, multipliers[1]),
+                weight_class: classes[i % classes.len()],
+                age: frac(i, multipliers[2]),
+                significant_strikes_lpm: frac(i, multipliers[3]),
+                strike_diff: frac(i, multipliers[4]),
+                takedown_lpm: frac(i, multipliers[5]),
+                submission_lpm: frac(i, multipliers[6]),
+                takedown_accuracy: frac(i, multipliers[7]),
+                takedown_defense: frac(i, multipliers[8]),
+                win_rate: t,
+                is_imputed: if i % 5 < 2 { 1.0 } else { 0.0 },
+            }
+        }).collect();
+
+        for reg in [Regularization::Ridge(0.1), Regularization::ElasticNet(0.1)] {
+            let coefs = train_model(&recs, reg).expect("regularized training failed");
+            let wh = coefs.iter().find(|c| c.name == "weight_height_ratio").unwrap();
+            assert_eq!(wh.std_error, 0.0, "SE isn't well-defined for a penalized fit");
+            // With std_error 0, ci_low/ci_high collapse to the coefficient itself.
+            assert_eq!(wh.ci_low, wh.coefficient);
+            assert_eq!(wh.ci_high, wh.coefficient);
+            assert!(wh.coefficient.is_finite());
+        }
+    }
+
+    /// MODEL: `train_ranker` (`--objective rank`) learns which feature separates better fighters
+    /// from worse ones via pairwise comparisons; on a dataset where `weight_height_ratio` tracks
+    /// `win_rate` almost exactly, it should come out with a positive weight. A dataset with a
+    /// constant `win_rate` has no pair clearing the margin, so it must error rather than panic.
+    #[test]
+    fn test_train_ranker_ranks_the_win_rate_driving_feature() {
+        let stances = [Stance::Orthodox, Stance::Southpaw, Stance::Switch];
+        let classes = [
+            WeightClass::Flyweight, WeightClass::Bantamweight, WeightClass::Featherweight,
+            WeightClass::Lightweight, WeightClass::Welterweight, WeightClass::Middleweight,
+            WeightClass::LightHeavyweight, WeightClass::Heavyweight,
+        ];
+        let multipliers = [0.414_f32, 0.732, 0.236, 0.890, 0.577, 0.347, 0.123, 0.951, 0.271];
+        let frac = |i: usize, m: f32| (i as f32 * m).fract();
+
+        let make_recs = |constant_win_rate: bool| -> Vec<CleanRecord> {
+            (0..30).map(|i| {
+                let stance = stances[i % stances.len()];
+                let (is_orthodox, is_southpaw, is_switch) = match stance {
+                    Stance::Orthodox => (1.0, 0.0, 0.0),
+                    Stance::Southpaw => (0.0, 1.0, 0.0),
+                    Stance::Switch   => (0.0, 0.0, 1.0),
+                };
+                let t = i as f32 / 29.0;
+                CleanRecord {
+                    stance,
+                    is_orthodox, is_southpaw, is_switch,
+                    weight_height_ratio: t,
+                    reach_height_ratio: frac(i, multipliers[0]),
+                    submission_per_takedown: frac(i, multipliers[1]),
+                    weight_class: classes[i % classes.len()],
+                    age: frac(i, multipliers[2]),
+                    significant_strikes_lpm: frac(i, multipliers[3]),
+                    strike_diff: frac(i, multipliers[4]),
+                    takedown_lpm: frac(i, multipliers[5]),
+                    submission_lpm: frac(i, multipliers[6]),
+                    takedown_accuracy: frac(i, multipliers[7]),
+                    takedown_defense: frac(i, multipliers[8]),
+                    win_rate: if constant_win_rate { 0.5 } else { t },
+                    is_imputed: if i % 5 < 2 { 1.0 } else { 0.0 },
+                }
+            }).collect()
+        };
+
+        let recs = make_recs(false);
+        let coefs = train_ranker(&recs).expect("ranker training failed");
+        let wh = coefs.iter().find(|c| c.name == "weight_height_ratio").unwrap();
+        assert!(wh.coefficient > 0.0, "weight_height_ratio should rank as a positive driver of win_rate");
+        assert_eq!(wh.std_error, 0.0, "SE isn't meaningful for the synthesized ±1-labeled dataset");
+
+        let constant = make_recs(true);
+        train_ranker(&constant)
+            .expect_err("a constant win_rate should leave no pair clearing the margin");
+    }
+
+    /// MODEL: on a tiny record set, the synthesized ±1-labeled pairwise dataset can end up with
+    /// fewer rows than surviving feature columns (here: 3 records give at most 3 pairs, i.e. 6
+    /// rows, against 11 columns once the 9 constant stance/weight-class one-hots are dropped).
+    /// `LinearRegression::fit` doesn't reject that as under-determined, it just returns wildly
+    /// scaled coefficients, so `train_ranker` must guard against it itself and return an `Err`.
+    #[test]
+    fn test_train_ranker_rejects_rank_deficient_pairwise_fit() {
+        let recs: Vec<CleanRecord> = [0.1_f32, 0.5, 0.9].iter().enumerate().map(|(i, &win_rate)| {
             CleanRecord {
                 stance: Stance::Orthodox,
                 is_orthodox: 1.0, is_southpaw: 0.0, is_switch: 0.0,
-                weight_height_ratio: 1.0, reach_height_ratio: 0.0,
-                submission_per_takedown: 0.0, weight_class: WeightClass::Bantamweight,
-                age: 0.0, significant_strikes_lpm: 0.0, strike_diff: 0.0,
-                takedown_lpm: 0.0, submission_lpm: 0.0,
-                takedown_accuracy: 0.0, takedown_defense: 0.0,
-                win_rate: 1.0,
-            },
+                weight_height_ratio: win_rate,
+                reach_height_ratio: 0.1 + i as f32 * 0.2,
+                submission_per_takedown: 0.2 + i as f32 * 0.1,
+                weight_class: WeightClass::Lightweight,
+                age: 0.3 + i as f32 * 0.05,
+                significant_strikes_lpm: 0.4 + i as f32 * 0.15,
+                strike_diff: 0.5 - i as f32 * 0.1,
+                takedown_lpm: 0.1 + i as f32 * 0.25,
+                submission_lpm: 0.2 + i as f32 * 0.2,
+                takedown_accuracy: 0.3 + i as f32 * 0.1,
+                takedown_defense: 0.4 + i as f32 * 0.05,
+                win_rate,
+                is_imputed: 0.0,
+            }
+        }).collect();
+
+        train_ranker(&recs)
+            .expect_err("3 records give a rank-deficient pairwise system and should be rejected, not numerically exploded");
+    }
+
+    /// MODEL: a dataset where every row survived cleaning intact (`is_imputed` constant 0.0) is
+    /// the common case — the default CLI path, or `--impute drop` on a complete CSV — and must
+    /// not make the design matrix singular. `train_model` (every `Regularization`), `train_ranker`,
+    /// and `cross_validate` all build that same matrix, so all three must train on it rather than
+    /// erroring out.
+    #[test]
+    fn test_train_model_handles_constant_is_imputed() {
+        let stances = [Stance::Orthodox, Stance::Southpaw, Stance::Switch];
+        let classes = [
+            WeightClass::Flyweight, WeightClass::Bantamweight, WeightClass::Featherweight,
+            WeightClass::Lightweight, WeightClass::Welterweight, WeightClass::Middleweight,
+            WeightClass::LightHeavyweight, WeightClass::Heavyweight,
+        ];
+        let multipliers = [0.414_f32, 0.732, 0.236, 0.890, 0.577, 0.347, 0.123, 0.951, 0.271];
+        let frac = |i: usize, m: f32| (i as f32 * m).fract();
+
+        let recs: Vec<CleanRecord> = (0..30).map(|i| {
+            let stance = stances[i % stances.len()];
+            let (is_orthodox, is_southpaw, is_switch) = match stance {
+                Stance::Orthodox => (1.0, 0.0, 0.0),
+                Stance::Southpaw => (0.0, 1.0, 0.0),
+                Stance::Switch   => (0.0, 0.0, 1.0),
+            };
+            let t = i as f32 / 29.0;
+            CleanRecord {
+                stance,
+                is_orthodox, is_southpaw, is_switch,
+                weight_height_ratio: t,
+                reach_height_ratio: frac(i, multipliers[0]),
+                submission_per_takedown: frac(i, multipliers[1]),
+                weight_class: classes[i % classes.len()],
+                age: frac(i, multipliers[2]),
+                significant_strikes_lpm: frac(i, multipliers[3]),
+                strike_diff: frac(i, multipliers[4]),
+                takedown_lpm: frac(i, multipliers[5]),
+                submission_lpm: frac(i, multipliers[6]),
+                takedown_accuracy: frac(i, multipliers[7]),
+                takedown_defense: frac(i, multipliers[8]),
+                win_rate: t,
+                // Every row survived cleaning intact: no missingness to report.
+                is_imputed: 0.0,
+            }
+        }).collect();
+
+        let is_imputed_is_zeroed = |coefs: &[crate::model::CoefficientEstimate]| {
+            let c = coefs.iter().find(|c| c.name == "is_imputed").unwrap();
+            assert_eq!(c.coefficient, 0.0);
+            assert_eq!(c.std_error, 0.0);
+            assert_eq!(c.ci_low, 0.0);
+            assert_eq!(c.ci_high, 0.0);
+        };
+
+        let ols = train_model(&recs, Regularization::None).expect("OLS training should not abort on a constant is_imputed column");
+        is_imputed_is_zeroed(&ols);
+
+        let ridge = train_model(&recs, Regularization::Ridge(0.1)).expect("ridge training should not abort on a constant is_imputed column");
+        is_imputed_is_zeroed(&ridge);
+
+        let elasticnet = train_model(&recs, Regularization::ElasticNet(0.1)).expect("elastic-net training should not abort on a constant is_imputed column");
+        is_imputed_is_zeroed(&elasticnet);
+
+        let ranked = train_ranker(&recs).expect("pairwise ranking should not abort on a constant is_imputed column");
+        is_imputed_is_zeroed(&ranked);
+
+        cross_validate(&recs, 3).expect("cross-validation should not abort on a constant is_imputed column");
+    }
+
+    /// MODEL: when many one-hot columns are dropped for zero variance (here: every record shares
+    /// the same stance and weight class, dropping `is_southpaw`, `is_switch`, and all 7 `wc_*`
+    /// columns, leaving 11 of the 20 `NUM_FEATURES`), the SE/CI degrees of freedom must be driven
+    /// by the number of columns actually kept, not the fixed `NUM_FEATURES`. `n = 15` sits between
+    /// `keep_cols.len() + 1 = 12` and `NUM_FEATURES + 1 = 21`, so this would spuriously fail the
+    /// old "need more than p+1 records" guard even though the effective model has plenty of data.
+    #[test]
+    fn test_train_model_se_dof_uses_kept_columns_not_num_features() {
+        let multipliers = [0.414_f32, 0.732, 0.236, 0.890, 0.577, 0.347, 0.123, 0.951, 0.271];
+        let frac = |i: usize, m: f32| (i as f32 * m).fract();
+
+        let recs: Vec<CleanRecord> = (0..15).map(|i| {
+            let t = i as f32 / 14.0;
             CleanRecord {
                 stance: Stance::Orthodox,
                 is_orthodox: 1.0, is_southpaw: 0.0, is_switch: 0.0,
-                weight_height_ratio: 0.0, reach_height_ratio: 0.0,
-                submission_per_takedown: 0.0, weight_class: WeightClass::Bantamweight,
-                age: 0.0, significant_strikes_lpm: 0.0, strike_diff: 0.0,
-                takedown_lpm: 0.0, submission_lpm: 0.0,
-                takedown_accuracy: 0.0, takedown_defense: 0.0,
-                win_rate: 0.0,
-            },
+                weight_height_ratio: t,
+                reach_height_ratio: frac(i, multipliers[0]),
+                submission_per_takedown: frac(i, multipliers[1]),
+                weight_class: WeightClass::Lightweight,
+                age: frac(i, multipliers[2]),
+                significant_strikes_lpm: frac(i, multipliers[3]),
+                strike_diff: frac(i, multipliers[4]),
+                takedown_lpm: frac(i, multipliers[5]),
+                submission_lpm: frac(i, multipliers[6]),
+                takedown_accuracy: frac(i, multipliers[7]),
+                takedown_defense: frac(i, multipliers[8]),
+                win_rate: t,
+                is_imputed: if i % 5 < 2 { 1.0 } else { 0.0 },
+            }
+        }).collect();
+
+        let coefs = train_model(&recs, Regularization::None)
+            .expect("15 records should be enough once dof accounts for the 9 dropped one-hot columns");
+
+        for dropped in ["is_southpaw", "is_switch", "wc_bantamweight", "wc_heavyweight"] {
+            let c = coefs.iter().find(|c| c.name == dropped).unwrap();
+            assert_eq!(c.std_error, 0.0, "{dropped} was dropped for zero variance, its SE should be 0");
+        }
+
+        let wh = coefs.iter().find(|c| c.name == "weight_height_ratio").unwrap();
+        assert!(wh.std_error.is_finite() && wh.std_error > 0.0, "kept columns should get a real, finite SE");
+    }
+
+    /// MODEL: two numeric features that are exact linear combinations of each other (rather than
+    /// each individually constant) aren't caught by the zero-variance drop, so `X^T X` is still
+    /// singular once the intercept is augmented in. `train_model` should return a clean `Err`
+    /// describing that, not panic.
+    #[test]
+    fn test_train_model_reports_genuine_collinearity() {
+        let stances = [Stance::Orthodox, Stance::Southpaw, Stance::Switch];
+        let classes = [
+            WeightClass::Flyweight, WeightClass::Bantamweight, WeightClass::Featherweight,
+            WeightClass::Lightweight, WeightClass::Welterweight, WeightClass::Middleweight,
+            WeightClass::LightHeavyweight, WeightClass::Heavyweight,
         ];
-        let coefs = train_model(&recs).expect("training failed");
-        let wh = coefs.iter()
-            .find(|&(name, _)| name == "weight_height_ratio")
-            .unwrap().1;
-        assert!(wh > 0.0, "Expected positive coefficient for weight_height_ratio");
+
+        let recs: Vec<CleanRecord> = (0..30).map(|i| {
+            let stance = stances[i % stances.len()];
+            let (is_orthodox, is_southpaw, is_switch) = match stance {
+                Stance::Orthodox => (1.0, 0.0, 0.0),
+                Stance::Southpaw => (0.0, 1.0, 0.0),
+                Stance::Switch   => (0.0, 0.0, 1.0),
+            };
+            let t = i as f32 / 29.0;
+            CleanRecord {
+                stance,
+                is_orthodox, is_southpaw, is_switch,
+                weight_height_ratio: t,
+                // Exactly tracks weight_height_ratio: collinear, even though neither column is
+                // individually constant.
+                reach_height_ratio: t,
+                submission_per_takedown: (i as f32 * 0.732).fract(),
+                weight_class: classes[i % classes.len()],
+                age: (i as f32 * 0.236).fract(),
+                significant_strikes_lpm: (i as f32 * 0.890).fract(),
+                strike_diff: (i as f32 * 0.577).fract(),
+                takedown_lpm: (i as f32 * 0.347).fract(),
+                submission_lpm: (i as f32 * 0.123).fract(),
+                takedown_accuracy: (i as f32 * 0.951).fract(),
+                takedown_defense: (i as f32 * 0.271).fract(),
+                win_rate: t,
+                is_imputed: if i % 5 < 2 { 1.0 } else { 0.0 },
+            }
+        }).collect();
+
+        train_model(&recs, Regularization::None)
+            .expect_err("genuinely collinear numeric columns should not make the fit panic");
+    }
+
+    /// PREPROCESS: `feature_min_max`'s reduction (parallel under the `parallel` feature, serial
+    /// otherwise, see `preprocess_with_scaler`) must find the true min and max across every
+    /// record, not just a prefix/suffix — min-max normalization maps the lowest reach_height_ratio
+    /// to 0.0 and the highest to 1.0 regardless of which record holds them.
+    #[test]
+    fn test_preprocess_minmax_normalizes_full_range() -> Result<(), Box<dyn Error>> {
+        let (fixture, mut f) = TempCsv::create("test_minmax.csv")?;
+        let path = fixture.as_str();
+        writeln!(&mut f, concat!(
+            "name,nickname,wins,losses,draws,",
+            "height_cm,weight_in_kg,reach_in_cm,stance,",
+            "date_of_birth,",
+            "significant_strikes_landed_per_minute,",
+            "significant_striking_accuracy,",
+            "significant_strikes_absorbed_per_minute,",
+            "significant_strike_defence,",
+            "average_takedowns_landed_per_15_minutes,",
+            "takedown_accuracy,takedown_defense,",
+            "average_submissions_attempted_per_15_minutes\n"
+        ))?;
+        // Same height for all three rows; reach alone varies, so reach_height_ratio's min/max
+        // come straight from reach_in_cm: 170 (lowest), 220 (highest), 190 (in between).
+        for reach in [190.0, 170.0, 220.0] {
+            writeln!(&mut f, "F,,10,2,1,180.0,80.0,{reach},Orthodox,1990-01-01,5.0,0.5,3.0,0.6,30.0,0.4,0.7,15.0")?;
+        }
+
+        let raw = load_csv(path)?;
+        let cleaned = preprocess(&raw, ImputationStrategy::Mean, NormalizationStrategy::MinMax);
+        assert_eq!(cleaned.len(), 3);
+
+        let lowest = cleaned.iter().find(|r| r.reach_height_ratio == 0.0);
+        let highest = cleaned.iter().find(|r| r.reach_height_ratio == 1.0);
+        assert!(lowest.is_some(), "lowest reach should normalize to 0.0");
+        assert!(highest.is_some(), "highest reach should normalize to 1.0");
+
+        Ok(())
+    }
+
+    /// PREPROCESS: under `ImputationStrategy::Drop`, any row needing a fill (missing weight, or
+    /// an unrecognized stance) is dropped outright rather than imputed, unlike `Mean`, which
+    /// fills the missing weight and falls back to the modal stance instead of dropping the row.
+    #[test]
+    fn test_drop_imputation_discards_rows_mean_fills_them() -> Result<(), Box<dyn Error>> {
+        let (fixture, mut f) = TempCsv::create("test_drop_impute.csv")?;
+        let path = fixture.as_str();
+        writeln!(&mut f, concat!(
+            "name,nickname,wins,losses,draws,",
+            "height_cm,weight_in_kg,reach_in_cm,stance,",
+            "date_of_birth,",
+            "significant_strikes_landed_per_minute,",
+            "significant_striking_accuracy,",
+            "significant_strikes_absorbed_per_minute,",
+            "significant_strike_defence,",
+            "average_takedowns_landed_per_15_minutes,",
+            "takedown_accuracy,takedown_defense,",
+            "average_submissions_attempted_per_15_minutes\n"
+        ))?;
+        // two complete rows, both Orthodox, so Orthodox is the modal stance
+        writeln!(&mut f, concat!(
+            "A,,10,2,1,180.0,90.0,190.0,Orthodox,1990-01-01,",
+            "5.0,0.5,3.0,0.6,30.0,0.4,0.7,15.0"
+        ))?;
+        writeln!(&mut f, concat!(
+            "B,,9,3,1,178.0,85.0,188.0,Orthodox,1991-01-01,",
+            "4.5,0.45,2.8,0.55,28.0,0.38,0.65,14.0"
+        ))?;
+        // missing weight
+        writeln!(&mut f, concat!(
+            "C,,8,3,1,180.0,,190.0,Southpaw,1992-06-01,",
+            "4.0,0.4,2.0,0.5,20.0,0.3,0.6,10.0"
+        ))?;
+        // unrecognized stance
+        writeln!(&mut f, concat!(
+            "D,,5,5,0,180.0,80.0,190.0,Unknown,1991-01-01,",
+            "3.0,0.3,2.5,0.5,10.0,0.2,0.5,5.0"
+        ))?;
+
+        let raw = load_csv(path)?;
+
+        let dropped = preprocess(&raw, ImputationStrategy::Drop, NormalizationStrategy::MinMax);
+        assert_eq!(dropped.len(), 2, "rows C and D should be dropped outright under Drop");
+
+        let filled = preprocess(&raw, ImputationStrategy::Mean, NormalizationStrategy::MinMax);
+        assert_eq!(filled.len(), 4, "Mean should fill rather than drop C and D");
+        let d = filled.iter().find(|r| r.is_orthodox == 1.0 && r.is_imputed == 1.0).unwrap();
+        assert_eq!(d.is_orthodox, 1.0, "D's unrecognized stance should fall back to the modal stance (Orthodox)");
+
+        Ok(())
+    }
+
+    /// PREPROCESS: `ZScore` normalizes to zero mean/unit (population) standard deviation, and
+    /// `Robust` to zero median with an interquartile-range spread — distinct formulas from
+    /// `MinMax`, exercised here on the same reach-driven ratio as the min-max test above.
+    #[test]
+    fn test_preprocess_zscore_and_robust_normalize_differently_than_minmax() -> Result<(), Box<dyn Error>> {
+        let (fixture, mut f) = TempCsv::create("test_zscore_robust.csv")?;
+        let path = fixture.as_str();
+        writeln!(&mut f, concat!(
+            "name,nickname,wins,losses,draws,",
+            "height_cm,weight_in_kg,reach_in_cm,stance,",
+            "date_of_birth,",
+            "significant_strikes_landed_per_minute,",
+            "significant_striking_accuracy,",
+            "significant_strikes_absorbed_per_minute,",
+            "significant_strike_defence,",
+            "average_takedowns_landed_per_15_minutes,",
+            "takedown_accuracy,takedown_defense,",
+            "average_submissions_attempted_per_15_minutes\n"
+        ))?;
+        // Same height for all four rows; reach_height_ratio is then reach/180, i.e. 1.0, 1.5, 2.0, 2.5.
+        for reach in [180.0, 270.0, 360.0, 450.0] {
+            writeln!(&mut f, "F,,10,2,1,180.0,80.0,{reach},Orthodox,1990-01-01,5.0,0.5,3.0,0.6,30.0,0.4,0.7,15.0")?;
+        }
+        let raw = load_csv(path)?;
+
+        // mean = 1.75, population std = sqrt(((0.75)^2+(0.25)^2+(0.25)^2+(0.75)^2)/4) = sqrt(0.325) ≈ 0.5701
+        let zscored = preprocess(&raw, ImputationStrategy::Mean, NormalizationStrategy::ZScore);
+        let mean = zscored.iter().map(|r| r.reach_height_ratio).sum::<f32>() / zscored.len() as f32;
+        assert!(mean.abs() < 1e-4, "z-scored feature should have ~0 mean, got {mean}");
+        let lowest = zscored.iter().map(|r| r.reach_height_ratio).fold(f32::INFINITY, f32::min);
+        assert!(lowest < -1.0, "lowest ratio should land well below 0 after z-scoring, got {lowest}");
+
+        // median = 1.75, Q1 = 1.1875, Q3 = 2.3125, IQR = 1.125
+        let robust = preprocess(&raw, ImputationStrategy::Mean, NormalizationStrategy::Robust);
+        // The two middle rows (ratio 1.5 and 2.0) sit symmetrically around the median (1.75), so
+        // their robust-normalized values should be negatives of each other.
+        let mut sorted: Vec<f32> = robust.iter().map(|r| r.reach_height_ratio).collect();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert!((sorted[1] + sorted[2]).abs() < 1e-4, "middle two rows should be symmetric around the median");
+
+        Ok(())
+    }
+
+    /// PREPROCESS: `preprocess_with_scaler` returns the fitted `FeatureScaler` alongside the
+    /// cleaned records, and `FeatureScaler::transform` reapplies that exact same normalization
+    /// (not a freshly-fit one) to a later, unseen batch — scoring a new fighter against a model
+    /// trained on an earlier one, rather than normalizing it against its own unrelated range.
+    #[test]
+    fn test_feature_scaler_reapplies_fitted_scale_to_unseen_records() -> Result<(), Box<dyn Error>> {
+        let (fixture, mut f) = TempCsv::create("test_scaler.csv")?;
+        let path = fixture.as_str();
+        writeln!(&mut f, concat!(
+            "name,nickname,wins,losses,draws,",
+            "height_cm,weight_in_kg,reach_in_cm,stance,",
+            "date_of_birth,",
+            "significant_strikes_landed_per_minute,",
+            "significant_striking_accuracy,",
+            "significant_strikes_absorbed_per_minute,",
+            "significant_strike_defence,",
+            "average_takedowns_landed_per_15_minutes,",
+            "takedown_accuracy,takedown_defense,",
+            "average_submissions_attempted_per_15_minutes\n"
+        ))?;
+        // Same height for all three rows, so reach_height_ratio's min/max come straight from
+        // reach_in_cm: 170 -> 0.944444, 220 -> 1.222222 (spread 0.277778).
+        for reach in [190.0, 170.0, 220.0] {
+            writeln!(&mut f, "F,,10,2,1,180.0,80.0,{reach},Orthodox,1990-01-01,5.0,0.5,3.0,0.6,30.0,0.4,0.7,15.0")?;
+        }
+        let raw = load_csv(path)?;
+        let (_reference, scaler) = preprocess_with_scaler(&raw, ImputationStrategy::Mean, NormalizationStrategy::MinMax);
+
+        // An unseen record with a ratio inside the fitted range normalizes against it, not
+        // against its own (single-record, zero-spread) range.
+        let mut unseen = vec![CleanRecord {
+            stance: Stance::Orthodox,
+            is_orthodox: 1.0, is_southpaw: 0.0, is_switch: 0.0,
+            weight_height_ratio: 0.5,
+            reach_height_ratio: 0.944444 + 0.277778 * 0.5, // halfway through the fitted range
+            submission_per_takedown: 0.0,
+            weight_class: WeightClass::Lightweight,
+            age: 0.0,
+            significant_strikes_lpm: 0.0,
+            strike_diff: 0.0,
+            takedown_lpm: 0.0,
+            submission_lpm: 0.0,
+            takedown_accuracy: 0.0,
+            takedown_defense: 0.0,
+            win_rate: 0.0,
+            is_imputed: 0.0,
+        }];
+        scaler.transform(&mut unseen);
+        assert!(
+            (unseen[0].reach_height_ratio - 0.5).abs() < 1e-4,
+            "expected ~0.5 (halfway through the fitted range), got {}",
+            unseen[0].reach_height_ratio
+        );
+
+        // An unseen ratio well outside the fitted range is clamped to [0,1] under MinMax rather
+        // than escaping it.
+        unseen[0].reach_height_ratio = 5.0;
+        scaler.transform(&mut unseen);
+        assert_eq!(unseen[0].reach_height_ratio, 1.0, "out-of-range ratio should clamp to 1.0");
+
+        Ok(())
+    }
+
+    /// CROSS-VALIDATE: on a dataset where `weight_height_ratio` tracks `win_rate` almost exactly,
+    /// k-fold cross-validation should report every fold's R^2/RMSE plus a sensible mean, and
+    /// should reject a fold count larger than the number of surviving records.
+    #[test]
+    fn test_cross_validate_reports_fold_metrics() {
+        let stances = [Stance::Orthodox, Stance::Southpaw, Stance::Switch];
+        let classes = [
+            WeightClass::Flyweight, WeightClass::Bantamweight, WeightClass::Featherweight,
+            WeightClass::Lightweight, WeightClass::Welterweight, WeightClass::Middleweight,
+            WeightClass::LightHeavyweight, WeightClass::Heavyweight,
+        ];
+        let multipliers = [0.414_f32, 0.732, 0.236, 0.890, 0.577, 0.347, 0.123, 0.951, 0.271];
+        let frac = |i: usize, m: f32| (i as f32 * m).fract();
+
+        let recs: Vec<CleanRecord> = (0..30).map(|i| {
+            let stance = stances[i % stances.len()];
+            let (is_orthodox, is_southpaw, is_switch) = match stance {
+                Stance::Orthodox => (1.0, 0.0, 0.0),
+                Stance::Southpaw => (0.0, 1.0, 0.0),
+                Stance::Switch   => (0.0, 0.0, 1.0),
+            };
+            let t = i as f32 / 29.0;
+            CleanRecord {
+                stance,
+                is_orthodox, is_southpaw, is_switch,
+                weight_height_ratio: t,
+                reach_height_ratio: frac(i, multipliers[0]),
+                submission_per_takedown: frac(i, multipliers[1]),
+                weight_class: classes[i % classes.len()],
+                age: frac(i, multipliers[2]),
+                significant_strikes_lpm: frac(i, multipliers[3]),
+                strike_diff: frac(i, multipliers[4]),
+                takedown_lpm: frac(i, multipliers[5]),
+                submission_lpm: frac(i, multipliers[6]),
+                takedown_accuracy: frac(i, multipliers[7]),
+                takedown_defense: frac(i, multipliers[8]),
+                win_rate: t,
+                is_imputed: if i % 5 < 2 { 1.0 } else { 0.0 },
+            }
+        }).collect();
+
+        let summary = cross_validate(&recs, 5).expect("cross-validation failed");
+        assert_eq!(summary.folds.len(), 5);
+        assert!(summary.r2_mean.is_finite() && summary.rmse_mean >= 0.0);
+        assert!(summary.rmse_std >= 0.0);
+
+        cross_validate(&recs, recs.len() + 1)
+            .expect_err("should error cleanly when fewer records survive than requested folds");
+    }
+
+    /// PREPROCESS: `resample_balanced` oversamples minority `WeightClass` buckets via Vose's
+    /// alias method, so a heavily skewed input (10 heavyweights, 2 flyweights) comes back roughly
+    /// balanced instead of reflecting the original 5:1 skew.
+    #[test]
+    fn test_resample_balanced_evens_out_skewed_weight_classes() {
+        let make = |weight_class: WeightClass| CleanRecord {
+            stance: Stance::Orthodox,
+            is_orthodox: 1.0, is_southpaw: 0.0, is_switch: 0.0,
+            weight_height_ratio: 0.5,
+            reach_height_ratio: 0.5,
+            submission_per_takedown: 0.0,
+            weight_class,
+            age: 0.0,
+            significant_strikes_lpm: 0.0,
+            strike_diff: 0.0,
+            takedown_lpm: 0.0,
+            submission_lpm: 0.0,
+            takedown_accuracy: 0.0,
+            takedown_defense: 0.0,
+            win_rate: 0.0,
+            is_imputed: 0.0,
+        };
+        let mut recs: Vec<CleanRecord> = (0..10).map(|_| make(WeightClass::Heavyweight)).collect();
+        recs.extend((0..2).map(|_| make(WeightClass::Flyweight)));
+
+        let target_n = 2000;
+        let idx = resample_balanced(&recs, target_n);
+        assert_eq!(idx.len(), target_n);
+        assert!(idx.iter().all(|&i| i < recs.len()), "every drawn index must be in bounds");
+
+        let mut counts: HashMap<usize, usize> = HashMap::new();
+        for i in idx {
+            *counts.entry(i).or_insert(0) += 1;
+        }
+        let flyweight_draws: usize = (10..12).map(|i| counts.get(&i).copied().unwrap_or(0)).sum();
+        let flyweight_share = flyweight_draws as f64 / target_n as f64;
+        assert!(
+            flyweight_share > 0.35,
+            "flyweight (minority) records should be drawn close to half the time after balancing, got {flyweight_share}"
+        );
+    }
+
+    /// PREPROCESS: `make_weight_driven_data` is `load_csv` + `preprocess` in one call, for callers
+    /// (e.g. scripts, tests) that just want cleaned records from a path without handling the two
+    /// steps themselves.
+    #[test]
+    fn test_make_weight_driven_data_loads_and_preprocesses() -> Result<(), Box<dyn Error>> {
+        let (fixture, mut f) = TempCsv::create("test_weight_driven.csv")?;
+        let path = fixture.as_str();
+        writeln!(&mut f, concat!(
+            "name,nickname,wins,losses,draws,",
+            "height_cm,weight_in_kg,reach_in_cm,stance,",
+            "date_of_birth,",
+            "significant_strikes_landed_per_minute,",
+            "significant_striking_accuracy,",
+            "significant_strikes_absorbed_per_minute,",
+            "significant_strike_defence,",
+            "average_takedowns_landed_per_15_minutes,",
+            "takedown_accuracy,takedown_defense,",
+            "average_submissions_attempted_per_15_minutes\n"
+        ))?;
+        writeln!(&mut f, "A,,10,2,1,180.0,70.0,190.0,Orthodox,1990-01-01,5.0,0.5,3.0,0.6,30.0,0.4,0.7,15.0")?;
+
+        let cleaned = make_weight_driven_data(path, ImputationStrategy::Mean, NormalizationStrategy::MinMax)?;
+        assert_eq!(cleaned.len(), 1);
+        assert_eq!(cleaned[0].weight_class, WeightClass::Lightweight);
+
+        Ok(())
     }
 } // end tests
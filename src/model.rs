@@ -3,31 +3,154 @@
 //! Provides a linear regression implementation that includes one-hot, absolute, and ratio features.
 
 use linfa::prelude::*;
+use linfa_elasticnet::ElasticNet;
 use linfa_linear::LinearRegression;
-use ndarray::{Array2, Array1};
+use ndarray::{s, Array1, Array2, Axis};
 use crate::preprocess::{CleanRecord, WeightClass};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
 use std::error::Error;
 
-/// Train a linear regression model on CleanRecord data and rank features by coefficient magnitude.
+/// Critical value for a 95% confidence interval under a standard normal distribution.
+const Z_95: f64 = 1.959964;
+
+/// Number of model features, not counting the intercept (2 stance flags + 7 weight-class flags
+/// + 10 numeric + 1 missingness indicator).
+pub(crate) const NUM_FEATURES: usize = 20;
+
+/// Human-readable names for the columns produced by `build_design_matrix`, in column order.
+pub(crate) const FEATURE_NAMES: [&str; NUM_FEATURES] = [
+    // stance
+    "is_southpaw", "is_switch",
+    // weight class
+    "wc_bantamweight", "wc_featherweight", "wc_lightweight",
+    "wc_welterweight", "wc_middleweight", "wc_light_heavyweight",
+    "wc_heavyweight",
+    // numeric
+    "weight_height_ratio", "reach_height_ratio", "submission_per_takedown",
+    "age", "significant_strikes_lpm", "strike_diff",
+    "takedown_lpm", "submission_lpm", "takedown_accuracy", "takedown_defense",
+    // missingness
+    "is_imputed",
+];
+
+/// A fitted regression coefficient together with its standard error and 95% confidence interval.
 ///
-/// # Inputs
-/// - `records`: slice of preprocessed `CleanRecord` rows.
+/// When the fit was standardized (see `FeatureStandardizer`), `coefficient` is in z-scored
+/// units (comparable across features regardless of their raw scale) and `raw_coefficient` is
+/// the back-mapped equivalent on the scale `model` actually received its input in — i.e. the
+/// feature as `preprocess` normalized it (e.g. `[0,1]` under the default `--normalize minmax`),
+/// not the fighter's true real-world units; when a feature wasn't standardized (e.g. a one-hot
+/// flag) the two are equal.
+#[derive(Debug, Clone, Serialize)]
+pub struct CoefficientEstimate {
+    pub name: String,
+    pub coefficient: f64,
+    pub raw_coefficient: f64,
+    pub std_error: f64,
+    pub ci_low: f64,
+    pub ci_high: f64,
+}
+
+/// Column indices (into the design matrix built by `build_design_matrix_subset`) that get
+/// z-scored before fitting: the 10 continuous numeric features. The stance/weight-class
+/// one-hots and the binary missingness indicator are already on a comparable 0/1 scale and are
+/// left alone.
+const STANDARDIZE_COLUMNS: [usize; 10] = [9, 10, 11, 12, 13, 14, 15, 16, 17, 18];
+
+/// Learned per-feature standardization (mean, std), fit once on the training design matrix so
+/// the same transform can be reapplied consistently and standardized coefficients can be
+/// mapped back to raw units.
+#[derive(Debug, Clone)]
+struct FeatureStandardizer {
+    means: Vec<f64>,
+    stds: Vec<f64>,
+}
+
+impl FeatureStandardizer {
+    /// Compute the mean and standard deviation of each column in `STANDARDIZE_COLUMNS`, over
+    /// `x`'s rows.
+    fn fit(x: &Array2<f64>) -> Self {
+        let means: Vec<f64> = STANDARDIZE_COLUMNS.iter()
+            .map(|&c| x.column(c).mean().unwrap_or(0.0))
+            .collect();
+        let stds: Vec<f64> = STANDARDIZE_COLUMNS.iter().zip(&means)
+            .map(|(&c, &mean)| {
+                let var = x.column(c).iter().map(|v| (v - mean).powi(2)).sum::<f64>() / x.nrows() as f64;
+                var.sqrt()
+            })
+            .collect();
+        FeatureStandardizer { means, stds }
+    }
+
+    /// Z-score each standardized column of `x` in place, using the fitted mean/std. A column
+    /// with zero variance (every training row equal) is left at 0.0 rather than dividing by zero.
+    fn transform(&self, x: &mut Array2<f64>) {
+        for (i, &c) in STANDARDIZE_COLUMNS.iter().enumerate() {
+            let (mean, std) = (self.means[i], self.stds[i]);
+            for v in x.column_mut(c).iter_mut() {
+                *v = if std > 0.0 { (*v - mean) / std } else { 0.0 };
+            }
+        }
+    }
+
+    /// Map a coefficient fit on (possibly standardized) design-matrix column `col` back to the
+    /// scale `build_design_matrix` produced it on: dividing by the column's std undoes this
+    /// struct's own z-scoring, but not `preprocess`'s earlier normalization, so the result is in
+    /// preprocessing-normalized units (e.g. `[0,1]` under `--normalize minmax`), not the
+    /// fighter's true units. Columns that weren't standardized here are returned unchanged.
+    fn to_raw_scale(&self, col: usize, coefficient: f64) -> f64 {
+        match STANDARDIZE_COLUMNS.iter().position(|&c| c == col) {
+            Some(i) if self.stds[i] > 0.0 => coefficient / self.stds[i],
+            Some(_) => 0.0,
+            None => coefficient,
+        }
+    }
+}
+
+/// Penalty applied when fitting feature coefficients. `None` reproduces the plain OLS fit, with
+/// exact standard errors and confidence intervals; `Ridge`/`ElasticNet` trade some of that exact
+/// inference for stability against the collinear one-hot columns, at a tunable penalty `lambda`.
+#[derive(Debug, Clone, Copy)]
+pub enum Regularization {
+    None,
+    Ridge(f64),
+    ElasticNet(f64),
+}
+
+/// L1 fraction of the elastic-net penalty; the remainder is the L2 (ridge) share.
+const ELASTICNET_L1_RATIO: f64 = 0.5;
+
+/// Build the (n x p) feature matrix and the target vector shared by every training routine.
 ///
-/// # Outputs
-/// - `Ok(Vec<(String, f64)>)`: sorted list of (feature_name, coefficient) pairs by descending |coefficient|.
-/// - `Err`: if model training fails.
-pub fn train_model(
-    records: &[CleanRecord]
-) -> Result<Vec<(String, f64)>, Box<dyn Error>> {
-    let n = records.len();
-    // Features: 2 stance flags + 7 weight-class flags + 10 numeric = 19
-    let p = 19;
+/// input: slice of preprocessed `CleanRecord` rows
+/// output: `(X, y)` where `X` is `n x NUM_FEATURES` and `y` is the `win_rate` target
+pub(crate) fn build_design_matrix(records: &[CleanRecord]) -> (Array2<f64>, Array1<f64>) {
+    let indices: Vec<usize> = (0..records.len()).collect();
+    build_design_matrix_subset(records, &indices)
+}
+
+/// Build the feature matrix and target vector for a subset of `records`, selected by `indices`.
+///
+/// input: the full slice of `CleanRecord` rows, plus the indices of the rows to include
+/// output: `(X, y)` restricted to those rows, in `indices` order
+///
+/// Used by cross-validation to build a fold's training/held-out matrices without duplicating
+/// the column layout that `build_design_matrix` encodes.
+pub(crate) fn build_design_matrix_subset(
+    records: &[CleanRecord],
+    indices: &[usize],
+) -> (Array2<f64>, Array1<f64>) {
+    let n = indices.len();
+    let p = NUM_FEATURES;
 
-    // Build feature matrix X (n × p) and target vector y (n)
     let mut x = Array2::<f64>::zeros((n, p));
     let mut y = Array1::<f64>::zeros(n);
 
-    for (i, r) in records.iter().enumerate() {
+    for (i, &idx) in indices.iter().enumerate() {
+        let r = &records[idx];
         // 1) Stance one-hot (Orthodox baseline)
         x[[i, 0]] = r.is_southpaw as f64;
         x[[i, 1]] = r.is_switch   as f64;
@@ -50,34 +173,390 @@ pub fn train_model(
         x[[i,16]] = r.submission_lpm          as f64;
         x[[i,17]] = r.takedown_accuracy       as f64;
         x[[i,18]] = r.takedown_defense        as f64;
-        // 4) Target variable
+        // 4) Missingness indicator
+        x[[i,19]] = r.is_imputed               as f64;
+        // 5) Target variable
         y[i] = r.win_rate as f64;
     }
 
-    // Fit linear regression with intercept
-    let dataset = Dataset::new(x, y);
+    (x, y)
+}
+
+/// Train a linear regression model on CleanRecord data and rank features by coefficient magnitude.
+///
+/// Numeric features are z-scored (see `FeatureStandardizer`) before fitting, so `coefficient` is
+/// in standardized units and comparable in magnitude across features regardless of their raw
+/// scale; `raw_coefficient` reports the back-mapped equivalent on the preprocessing-normalized
+/// scale (e.g. `[0,1]` under `--normalize minmax`) that `records` arrived in, not the fighter's
+/// true real-world units.
+///
+/// # Inputs
+/// - `records`: slice of preprocessed `CleanRecord` rows.
+/// - `regularization`: penalty to apply when fitting; `None` uses plain OLS with exact standard
+///   errors and confidence intervals, `Ridge`/`ElasticNet` fit a penalized model instead (whose
+///   standard errors and confidence intervals aren't well-defined in closed form, so those
+///   fields are reported as zero).
+///
+/// # Outputs
+/// - `Ok(Vec<CoefficientEstimate>)`: sorted list of coefficients, ordered by descending
+///   `|coefficient|`.
+/// - `Err`: if model training fails, or (in the `None` case) if `X^T X` is still singular after
+///   excluding zero-variance feature columns (e.g. two one-hot columns are collinear, or
+///   `n <= p`), which would make the covariance matrix undefined. A feature with no variance in
+///   the training data (e.g. `is_imputed` when every row survived cleanly, or when `--impute drop`
+///   leaves no imputed rows) is dropped from the design matrix before the fit itself runs, and
+///   reported with coefficient/SE/CI of 0, rather than making the fit abort.
+pub fn train_model(
+    records: &[CleanRecord],
+    regularization: Regularization,
+) -> Result<Vec<CoefficientEstimate>, Box<dyn Error>> {
+    let n = records.len();
+    let p = NUM_FEATURES;
+    let (mut x, y) = build_design_matrix(records);
+
+    let standardizer = FeatureStandardizer::fit(&x);
+    standardizer.transform(&mut x);
+
+    // A feature column with zero variance in this data (e.g. `is_imputed` when every row
+    // survived cleanly, or a weight-class one-hot absent from a small sample) makes `X` itself
+    // singular, so `LinearRegression`/`ElasticNet`/the ridge normal-equations solve all fail to
+    // fit. Drop such columns before fitting at all, not only from the later SE computation below.
+    let drop_cols = zero_variance_columns(&x);
+    let (x_fit, keep_cols) = drop_columns(&x, &drop_cols);
+    let dataset = Dataset::new(x_fit.clone(), y.clone());
+
+    let (intercept, coefs_fit): (f64, Array1<f64>) = match regularization {
+        Regularization::None => {
+            let model = LinearRegression::default().fit(&dataset)?;
+            (model.intercept(), model.params().to_owned())
+        }
+        Regularization::Ridge(lambda) => {
+            // linfa-elasticnet's coordinate-descent solver degenerates at `l1_ratio == 0.0`
+            // (pure L2), so ridge gets its own closed-form normal-equations solve instead of
+            // going through `ElasticNet` with a zeroed-out L1 share.
+            fit_ridge(&x_fit, &y, lambda)?
+        }
+        Regularization::ElasticNet(lambda) => {
+            let model = ElasticNet::params().penalty(lambda).l1_ratio(ELASTICNET_L1_RATIO).fit(&dataset)?;
+            (model.intercept(), model.hyperplane().to_owned())
+        }
+    };
+
+    // Scatter the reduced-fit coefficients back into the full `NUM_FEATURES` layout; a dropped
+    // (zero-variance) column gets coefficient 0, matching the fact that it had no effect on the fit.
+    let mut coefs = Array1::<f64>::zeros(p);
+    for (pos, &col) in keep_cols.iter().enumerate() {
+        coefs[col] = coefs_fit[pos];
+    }
+
+    // Exact standard errors/CIs only have a closed form for the unpenalized OLS fit.
+    let std_errors: Option<Array1<f64>> = match regularization {
+        Regularization::None => {
+            // Degrees of freedom are driven by the number of columns actually fit (`keep_cols`),
+            // not the fixed `NUM_FEATURES`: zero-variance one-hots dropped above (e.g. weight
+            // classes absent from a small sample) shouldn't count as estimated parameters, or
+            // every residual variance below would be divided by too small a denominator.
+            let p_eff = keep_cols.len();
+            if n <= p_eff + 1 {
+                return Err(format!(
+                    "need more than {} records to estimate standard errors for {} coefficients plus an intercept, got {}",
+                    p_eff + 1, p_eff, n
+                ).into());
+            }
+
+            // Augment X with an intercept column so residuals and the covariance matrix line up with the fit.
+            let mut xa = Array2::<f64>::zeros((n, p + 1));
+            xa.column_mut(0).fill(1.0);
+            xa.slice_mut(s![.., 1..]).assign(&x);
+
+            let mut beta = Array1::<f64>::zeros(p + 1);
+            beta[0] = intercept;
+            beta.slice_mut(s![1..]).assign(&coefs);
+
+            // residuals e = y - X*beta, residual variance s^2 = (e^T e) / (n - p_eff - 1)
+            let residuals = &y - &xa.dot(&beta);
+            let ss_res: f64 = residuals.iter().map(|e| e * e).sum();
+            let dof = (n - p_eff - 1) as f64;
+            let sigma2 = ss_res / dof;
+
+            // The same zero-variance columns already excluded from the fit above (e.g.
+            // `is_imputed` when every row survived cleanly) would make the augmented X^T X
+            // singular too: drop them from the matrix that gets inverted and report their
+            // SE/CI as 0 rather than aborting.
+            let keep_cols: Vec<usize> = std::iter::once(0)
+                .chain((1..=p).filter(|&c| !drop_cols[c - 1]))
+                .collect();
+
+            // Coefficient covariance matrix C = s^2 * (X^T X)^-1, restricted to the kept columns.
+            let xa_reduced = xa.select(Axis(1), &keep_cols);
+            let xtx = xa_reduced.t().dot(&xa_reduced);
+            let xtx_inv = invert_matrix(&xtx)
+                .ok_or("singular X^T X: one-hot columns are collinear or there are too few records")?;
+
+            let mut se = vec![0.0; p];
+            for (pos, &c) in keep_cols.iter().enumerate().skip(1) {
+                se[c - 1] = (sigma2 * xtx_inv[[pos, pos]]).max(0.0).sqrt();
+            }
+            Some(Array1::from(se))
+        }
+        _ => None,
+    };
+
+    let mut results: Vec<CoefficientEstimate> = FEATURE_NAMES
+        .iter()
+        .enumerate()
+        .map(|(j, &name)| {
+            let coefficient = coefs[j];
+            let std_error = std_errors.as_ref().map(|se| se[j]).unwrap_or(0.0);
+            CoefficientEstimate {
+                name: name.to_string(),
+                coefficient,
+                raw_coefficient: standardizer.to_raw_scale(j, coefficient),
+                std_error,
+                ci_low: coefficient - Z_95 * std_error,
+                ci_high: coefficient + Z_95 * std_error,
+            }
+        })
+        .collect();
+    results.sort_by(|a, b| b.coefficient.abs().partial_cmp(&a.coefficient.abs()).unwrap());
+
+    Ok(results)
+}
+
+/// Seed for sampling candidate pairs in `train_ranker`, so a given dataset always produces the
+/// same ranking across runs.
+const RANKER_SEED: u64 = 42;
+
+/// Number of random pairs to sample before margin-filtering and top-gap selection.
+const RANKER_SAMPLE_PAIRS: usize = 5000;
+
+/// Minimum `|win_rate_i - win_rate_j|` for a pair to be considered distinguishable.
+const RANKER_MARGIN: f64 = 0.05;
+
+/// Maximum number of pairs kept per anchor record, chosen by largest win-rate gap.
+const RANKER_TOP_PER_ANCHOR: usize = 50;
+
+/// Train a pairwise-ranking model (Hopkins & May PRO) instead of fitting OLS against raw
+/// `win_rate`: learns which features distinguish a *better* fighter from a *worse* one, rather
+/// than predicting an absolute win rate.
+///
+/// # Inputs
+/// - `records`: slice of preprocessed `CleanRecord` rows.
+///
+/// # Outputs
+/// - `Ok(Vec<CoefficientEstimate>)`: the learned ranking weights, in the same column layout and
+///   `(name, coefficient)` shape as `train_model`, ordered by descending `|coefficient|`.
+///   Standard errors and confidence intervals aren't meaningful for this synthesized
+///   ±1-labeled dataset, so they're reported as zero.
+/// - `Err`: if fewer than two records are given, or if no sampled pair clears the margin
+///   (e.g. `win_rate` is constant across all records).
+///
+/// # Algorithm
+/// Sample `RANKER_SAMPLE_PAIRS` random pairs of records (deduplicating symmetric pairs), discard
+/// any pair whose win-rate gap doesn't exceed `RANKER_MARGIN`, then keep the top
+/// `RANKER_TOP_PER_ANCHOR` surviving pairs per anchor record by gap magnitude. For each kept pair
+/// `(i, j)` with feature rows `x_i`, `x_j`, add two training examples: `(x_i - x_j, +1)` if
+/// `win_rate_i > win_rate_j`, and its negation `(x_j - x_i, -1)`. Fit an ordinary least-squares
+/// model on this ±1-labeled dataset; the resulting weight vector ranks features by how strongly
+/// they separate better fighters from worse ones.
+pub fn train_ranker(records: &[CleanRecord]) -> Result<Vec<CoefficientEstimate>, Box<dyn Error>> {
+    let n = records.len();
+    if n < 2 {
+        return Err("need at least 2 records to train a pairwise ranker".into());
+    }
+
+    let (x, _) = build_design_matrix(records);
+
+    // 1) Sample candidate pairs, deduplicating symmetric (i, j) / (j, i) draws.
+    let mut rng = StdRng::seed_from_u64(RANKER_SEED);
+    let mut seen_pairs: HashSet<(usize, usize)> = HashSet::new();
+    let mut candidates: Vec<(usize, usize, f64)> = Vec::new(); // (lo, hi, win_rate[lo] - win_rate[hi])
+    for _ in 0..RANKER_SAMPLE_PAIRS {
+        let i = rng.gen_range(0..n);
+        let j = rng.gen_range(0..n);
+        if i == j {
+            continue;
+        }
+        let (lo, hi) = if i < j { (i, j) } else { (j, i) };
+        if !seen_pairs.insert((lo, hi)) {
+            continue;
+        }
+        let gap = (records[lo].win_rate - records[hi].win_rate) as f64;
+        if gap.abs() <= RANKER_MARGIN {
+            continue;
+        }
+        candidates.push((lo, hi, gap));
+    }
+    if candidates.is_empty() {
+        return Err("no sampled pairs cleared the margin; win_rate may be constant across all records".into());
+    }
+
+    // 2) Keep only the top RANKER_TOP_PER_ANCHOR pairs per anchor, by gap magnitude.
+    candidates.sort_by(|a, b| b.2.abs().partial_cmp(&a.2.abs()).unwrap());
+    let mut per_anchor_count: HashMap<usize, usize> = HashMap::new();
+    let mut kept: Vec<(usize, usize, f64)> = Vec::new();
+    for (lo, hi, gap) in candidates {
+        let count = per_anchor_count.entry(lo).or_insert(0);
+        if *count >= RANKER_TOP_PER_ANCHOR {
+            continue;
+        }
+        *count += 1;
+        kept.push((lo, hi, gap));
+    }
+
+    // 3) Synthesize a ±1-labeled dataset of feature differences: two training examples per pair.
+    let p = NUM_FEATURES;
+    let mut xt = Array2::<f64>::zeros((kept.len() * 2, p));
+    let mut yt = Array1::<f64>::zeros(kept.len() * 2);
+    for (row, &(lo, hi, gap)) in kept.iter().enumerate() {
+        let d = &x.row(lo) - &x.row(hi);
+        let label = if gap > 0.0 { 1.0 } else { -1.0 };
+        xt.row_mut(2 * row).assign(&d);
+        yt[2 * row] = label;
+        xt.row_mut(2 * row + 1).assign(&(-&d));
+        yt[2 * row + 1] = -label;
+    }
+
+    // 4) Fit least squares on the synthesized ±1 labels; the weight vector is the ranking.
+    // A feature that's constant across `records` (e.g. `is_imputed` when every row survived
+    // cleanly) differences to an all-zero column here, which would make `xt` singular; drop it
+    // from the fit the same way `train_model` does, and report it with a 0 weight.
+    let drop_cols = zero_variance_columns(&xt);
+    let (xt_fit, keep_cols) = drop_columns(&xt, &drop_cols);
+
+    // On a small or heavily-filtered `kept` set, the synthesized pairwise system can have no more
+    // rows than columns (or otherwise be rank-deficient), and `LinearRegression::fit` doesn't
+    // error on that — it silently returns numerically exploded, meaningless coefficients instead.
+    // Require the same margin `train_model` does (more rows than parameters) before trusting it.
+    if xt_fit.nrows() <= keep_cols.len() {
+        return Err(format!(
+            "need more than {} sampled pairs to fit a ranker over {} surviving features, got {}",
+            keep_cols.len(), keep_cols.len(), xt_fit.nrows()
+        ).into());
+    }
+
+    let dataset = Dataset::new(xt_fit, yt);
     let model = LinearRegression::default().fit(&dataset)?;
+    let coefs_fit = model.params();
+
+    let mut coefs = Array1::<f64>::zeros(p);
+    for (pos, &col) in keep_cols.iter().enumerate() {
+        coefs[col] = coefs_fit[pos];
+    }
 
-    // Extract and sort coefficients by absolute value
-    let coefs = model.params();
-    let feature_names = [
-        // stance
-        "is_southpaw", "is_switch",
-        // weight class
-        "wc_bantamweight", "wc_featherweight", "wc_lightweight",
-        "wc_welterweight", "wc_middleweight", "wc_light_heavyweight",
-        "wc_heavyweight",
-        // numeric
-        "weight_height_ratio", "reach_height_ratio", "submission_per_takedown",
-        "age", "significant_strikes_lpm", "strike_diff",
-        "takedown_lpm", "submission_lpm", "takedown_accuracy", "takedown_defense",
-    ];
-    let mut results: Vec<(String, f64)> = feature_names
+    let mut results: Vec<CoefficientEstimate> = FEATURE_NAMES
         .iter()
-        .zip(coefs.iter())
-        .map(|(&name, &coef)| (name.to_string(), coef))
+        .enumerate()
+        .map(|(j, &name)| CoefficientEstimate {
+            name: name.to_string(),
+            coefficient: coefs[j],
+            raw_coefficient: coefs[j],
+            std_error: 0.0,
+            ci_low: 0.0,
+            ci_high: 0.0,
+        })
         .collect();
-    results.sort_by(|a, b| b.1.abs().partial_cmp(&a.1.abs()).unwrap());
+    results.sort_by(|a, b| b.coefficient.abs().partial_cmp(&a.coefficient.abs()).unwrap());
 
     Ok(results)
-}
\ No newline at end of file
+}
+
+/// Fit ridge regression by solving the penalized normal equations directly, rather than via
+/// `linfa-elasticnet` (whose coordinate-descent solver targets a mixed L1/L2 penalty and isn't
+/// reliable at a pure-L2 `l1_ratio` of 0).
+///
+/// input: design matrix `x` (n x p), target `y`, and the L2 penalty `lambda`
+/// output: `(intercept, coefficients)` minimizing `||y - X*beta||^2 + lambda * ||beta||^2`
+/// (the intercept itself is left unpenalized, as is conventional for ridge)
+fn fit_ridge(x: &Array2<f64>, y: &Array1<f64>, lambda: f64) -> Result<(f64, Array1<f64>), Box<dyn Error>> {
+    let n = x.nrows();
+    let p = x.ncols();
+
+    let mut xa = Array2::<f64>::zeros((n, p + 1));
+    xa.column_mut(0).fill(1.0);
+    xa.slice_mut(s![.., 1..]).assign(x);
+
+    let mut penalty = Array2::<f64>::eye(p + 1);
+    penalty[[0, 0]] = 0.0;
+
+    let xtx = xa.t().dot(&xa) + lambda * &penalty;
+    let xty = xa.t().dot(y);
+    let xtx_inv = invert_matrix(&xtx)
+        .ok_or("singular ridge normal equations: try a larger --regularization lambda")?;
+    let beta = xtx_inv.dot(&xty);
+
+    Ok((beta[0], beta.slice(s![1..]).to_owned()))
+}
+
+/// Column indices of `x` with (near-)zero variance across its rows — e.g. `is_imputed` when no
+/// row needed imputation, or a weight-class one-hot absent from a small sample. Such a column
+/// makes `X` (or `X^T X`) singular, so it must be excluded from whatever gets fit or inverted.
+pub(crate) fn zero_variance_columns(x: &Array2<f64>) -> Vec<bool> {
+    (0..x.ncols())
+        .map(|c| {
+            let mean = x.column(c).mean().unwrap_or(0.0);
+            let var = x.column(c).iter().map(|v| (v - mean).powi(2)).sum::<f64>() / x.nrows() as f64;
+            var < 1e-12
+        })
+        .collect()
+}
+
+/// Restrict `x` to the columns *not* flagged in `drop`, returning the reduced matrix alongside
+/// the original column index each kept column came from (so a fit on the reduced matrix can be
+/// scattered back into the full-width coefficient layout).
+pub(crate) fn drop_columns(x: &Array2<f64>, drop: &[bool]) -> (Array2<f64>, Vec<usize>) {
+    let keep_cols: Vec<usize> = (0..x.ncols()).filter(|&c| !drop[c]).collect();
+    (x.select(Axis(1), &keep_cols), keep_cols)
+}
+
+/// Invert a square matrix via Gauss-Jordan elimination with partial pivoting.
+///
+/// input: a square `Array2<f64>`
+/// output: `Some(inverse)`, or `None` if the matrix is singular (or numerically indistinguishable
+/// from singular, judged by a small pivot tolerance)
+fn invert_matrix(m: &Array2<f64>) -> Option<Array2<f64>> {
+    let n = m.nrows();
+    assert_eq!(n, m.ncols(), "invert_matrix requires a square matrix");
+
+    let mut a = m.clone();
+    let mut inv = Array2::<f64>::eye(n);
+
+    for col in 0..n {
+        // Partial pivot: swap in the largest-magnitude entry at or below the diagonal.
+        let (pivot_row, pivot_val) = (col..n)
+            .map(|row| (row, a[[row, col]].abs()))
+            .fold((col, 0.0_f64), |best, cur| if cur.1 > best.1 { cur } else { best });
+
+        if pivot_val < 1e-12 {
+            return None;
+        }
+        if pivot_row != col {
+            for k in 0..n {
+                a.swap([col, k], [pivot_row, k]);
+                inv.swap([col, k], [pivot_row, k]);
+            }
+        }
+
+        let pivot = a[[col, col]];
+        for k in 0..n {
+            a[[col, k]] /= pivot;
+            inv[[col, k]] /= pivot;
+        }
+
+        for row in 0..n {
+            if row == col {
+                continue;
+            }
+            let factor = a[[row, col]];
+            if factor == 0.0 {
+                continue;
+            }
+            for k in 0..n {
+                a[[row, k]] -= factor * a[[col, k]];
+                inv[[row, k]] -= factor * inv[[col, k]];
+            }
+        }
+    }
+
+    Some(inv)
+}
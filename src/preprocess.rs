@@ -1,7 +1,11 @@
-/// Data cleaning and preprocessing.
+//! Data cleaning and preprocessing.
 
 use crate::io::FighterRecord;
-use chrono::{Datelike, Local};
+use chrono::{Datelike, Local, NaiveDate};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 use std::str::FromStr;
 use std::error::Error;
 
@@ -29,6 +33,22 @@ impl FromStr for Stance {
     }
 }
 
+/// Strategy for handling a numeric or stance field that's missing (or NaN) in the raw CSV.
+#[derive(Debug, Clone, Copy)]
+pub enum ImputationStrategy {
+    /// Drop the whole fighter row if any field is missing, rather than filling it in.
+    Drop,
+    /// Fill a missing numeric with the mean of the column's present values, and a missing
+    /// stance with the modal stance across the batch.
+    Mean,
+    /// Fill a missing numeric with the median of the column's present values, and a missing
+    /// stance with the modal stance across the batch.
+    Median,
+    /// Fill every missing numeric with a fixed, caller-supplied value (stance still falls back
+    /// to the modal stance).
+    Constant(f32),
+}
+
 /// Weight class categories
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum WeightClass {
@@ -45,8 +65,14 @@ pub enum WeightClass {
 /// Resulting cleaned record with engineered features (per-minute rates); used to represent the cleaned rows
 #[derive(Debug)]
 pub struct CleanRecord {
+    // Kept alongside the one-hot flags below for callers that want the original category
+    // (e.g. debugging output); not read by `build_design_matrix`, which uses the one-hots instead.
+    #[allow(dead_code)]
     pub stance: Stance,
-    // One-hot stance flags
+    // One-hot stance flags; `is_orthodox` is the dropped reference category (kept for anyone
+    // inspecting a record directly) — `build_design_matrix` only reads `is_southpaw`/`is_switch`,
+    // the standard way to avoid collinearity with the intercept.
+    #[allow(dead_code)]
     pub is_orthodox:             f32,
     pub is_southpaw:             f32,
     pub is_switch:               f32,
@@ -66,154 +92,607 @@ pub struct CleanRecord {
     pub takedown_accuracy:       f32,
     pub takedown_defense:        f32,
     pub win_rate:                f32,
+    /// 1.0 if any numeric field on this record was missing and filled in by the imputation
+    /// pass, 0.0 if every field was present in the source CSV.
+    pub is_imputed:              f32,
 }
 
-/// Clean raw fighter records and engineer normalized features for modeling
-/// input: raw, CSV‑deserialized data
-/// output: valid records with one‑hot flags, ratios, rates, and normalized numerics
-/// logic: unwrap or drop invalid/NaN numeric fields; one‑hot encode stance;
-/// compute win_rate, age, weight/height & reach/height ratios, per‑minute rates, efficiency, weight_class;
-/// normalize all numeric features to [0,1]
-pub fn preprocess(records: &[FighterRecord]) -> Vec<CleanRecord> {
-    let today = Local::now().date_naive();
-    let mut cleaned = Vec::new();
+/// Compute the fill value for one numeric column under the given imputation strategy, using
+/// only the rows where the value is present (and not NaN).
+///
+/// input: raw records, a getter projecting each record to that column's `Option<f32>`, and the
+/// chosen strategy
+/// output: the value that should replace a missing entry in this column
+fn column_fill_value(
+    records: &[&FighterRecord],
+    getter: impl Fn(&FighterRecord) -> Option<f32>,
+    strategy: ImputationStrategy,
+) -> f32 {
+    match strategy {
+        // unused: rows with any missing field are dropped outright under `Drop`, see clean_record
+        ImputationStrategy::Drop => return 0.0,
+        ImputationStrategy::Constant(c) => return c,
+        ImputationStrategy::Mean | ImputationStrategy::Median => {}
+    }
+
+    let mut present: Vec<f32> = records.iter()
+        .filter_map(|r| getter(r))
+        .filter(|v| !v.is_nan())
+        .collect();
+    if present.is_empty() {
+        return 0.0;
+    }
 
+    match strategy {
+        ImputationStrategy::Mean => present.iter().sum::<f32>() / present.len() as f32,
+        ImputationStrategy::Median => {
+            present.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            let mid = present.len() / 2;
+            if present.len().is_multiple_of(2) {
+                (present[mid - 1] + present[mid]) / 2.0
+            } else {
+                present[mid]
+            }
+        }
+        ImputationStrategy::Drop | ImputationStrategy::Constant(_) => unreachable!("handled above"),
+    }
+}
+
+/// The most common parseable stance across `records`, used to fill in a missing or unrecognized
+/// stance under `Mean`/`Median`/`Constant` imputation; defaults to `Orthodox` if none parse.
+fn modal_stance(records: &[&FighterRecord]) -> Stance {
+    let mut counts = [0usize; 3]; // [Orthodox, Southpaw, Switch]
     for r in records {
-        // 1) unwrap or default numeric inputs
-        let weight = r.weight_in_kg.unwrap_or_default();
-        let height = r.height_cm.unwrap_or_default();
-        let reach  = r.reach_in_cm.unwrap_or_default();
-        if weight <= 0.0 || height <= 0.0 { continue; }
-        let s_lpm = r.significant_strikes_landed_per_minute.unwrap_or_default();
-        let s_abs = r.significant_strikes_absorbed_per_minute.unwrap_or_default();
-        let tkl15 = r.average_takedowns_landed_per_15_minutes.unwrap_or_default();
-        let sub15 = r.average_submissions_attempted_per_15_minutes.unwrap_or_default();
-        let td_acc= r.takedown_accuracy.unwrap_or_default();
-        let td_def= r.takedown_defense.unwrap_or_default();
-        // drop NaNs
-        if [weight, height, reach, s_lpm, s_abs, tkl15, sub15, td_acc, td_def]
-            .iter().any(|v| v.is_nan()) { continue; }
-
-        // 2) parse stance + one-hot
-        let stance = match r.stance.parse::<Stance>() {
-            Ok(s) => s,
-            Err(_) => continue,
-        };
-        let (iso, isp, iss) = match stance {
-            Stance::Orthodox => (1.0, 0.0, 0.0),
-            Stance::Southpaw => (0.0, 1.0, 0.0),
-            Stance::Switch   => (0.0, 0.0, 1.0),
-        };
-
-        // 3) compute win rate
-        let total = (r.wins + r.losses + r.draws) as f32;
-        let win_rate = if total > 0.0 { r.wins as f32 / total } else { 0.0 };
-
-        // compute age
-        let mut age = today.year() - r.date_of_birth.year();
-        if (today.month(), today.day()) < (r.date_of_birth.month(), r.date_of_birth.day()) {
-            age -= 1;
+        if let Ok(s) = r.stance.parse::<Stance>() {
+            counts[stance_index(s)] += 1;
         }
-        let age = age as f32;
-
-        // 4) engineer features
-        let weight_height_ratio = weight / height;
-        let reach_height_ratio  = reach / height;
-        // convert to per-minute rates
-        let takedown_lpm   = tkl15 / 15.0;
-        let submission_lpm = sub15 / 15.0;
-        // efficiency metric
-        let submission_per_takedown = if takedown_lpm > 0.0 { submission_lpm / takedown_lpm } else { 0.0 };
-        // weight class bucket
-        let weight_class = if weight < 56.7 {
-            WeightClass::Flyweight
-        } else if weight < 61.2 {
-            WeightClass::Bantamweight
-        } else if weight < 65.8 {
-            WeightClass::Featherweight
-        } else if weight < 70.3 {
-            WeightClass::Lightweight
-        } else if weight < 77.1 {
-            WeightClass::Welterweight
-        } else if weight < 83.9 {
-            WeightClass::Middleweight
-        } else if weight < 93.0 {
-            WeightClass::LightHeavyweight
-        } else {
-            WeightClass::Heavyweight
-        };
-
-        cleaned.push(CleanRecord {
-            stance,
-            is_orthodox:             iso,
-            is_southpaw:             isp,
-            is_switch:               iss,
-            weight_height_ratio,
-            reach_height_ratio,
-            submission_per_takedown,
-            weight_class,
-            age,
-            significant_strikes_lpm: s_lpm,
-            strike_diff:             s_lpm - s_abs,
-            takedown_lpm,
-            submission_lpm,
-            takedown_accuracy:       td_acc,
-            takedown_defense:        td_def,
-            win_rate,
-        });
-    }
-
-    // 5) normalize all numeric fields to [0,1]
-    /// Inputs: "records": mutable slice of `CleanRecord` to modify in place,
-    /// "getter": function to extract the raw feature value from a record,
-    /// "setter": function to assign the normalized value back into the record.
-    /// logic: Fold over all records to find the feature’s global `min` and `max`;
-    /// Loop through each record, compute "(value - min) / (max - min)"" (0 if min == max) and set it.
-    fn normalize(
-        records: &mut [CleanRecord],
-        getter: impl Fn(&CleanRecord) -> f32,
-        setter: impl Fn(&mut CleanRecord, f32),
-    ) {
-        // 1) Compute global min and max for this feature
-        let (min, max) = records.iter().fold(
-            (f32::INFINITY, f32::NEG_INFINITY),
-            |(mi, ma), r| {
-                let v = getter(r);
-                (mi.min(v), ma.max(v))
-            },
+    }
+    let (max_idx, _) = counts.iter().enumerate().max_by_key(|&(_, &c)| c).unwrap();
+    match max_idx {
+        0 => Stance::Orthodox,
+        1 => Stance::Southpaw,
+        _ => Stance::Switch,
+    }
+}
+
+fn stance_index(stance: Stance) -> usize {
+    match stance {
+        Stance::Orthodox => 0,
+        Stance::Southpaw => 1,
+        Stance::Switch => 2,
+    }
+}
+
+/// Resolve a raw `Option<f32>` field to a concrete value, filling in `fill` when the field is
+/// missing or NaN.
+///
+/// output: `(value, was_imputed)`
+fn resolve(value: Option<f32>, fill: f32) -> (f32, bool) {
+    match value {
+        Some(v) if !v.is_nan() => (v, false),
+        _ => (fill, true),
+    }
+}
+
+/// One fill value per numeric column, learned once from `records` before cleaning so every
+/// record can be resolved independently (a prerequisite for processing them in parallel).
+struct ColumnFills {
+    weight: f32,
+    height: f32,
+    reach:  f32,
+    s_lpm:  f32,
+    s_abs:  f32,
+    tkl15:  f32,
+    sub15:  f32,
+    td_acc: f32,
+    td_def: f32,
+    modal_stance: Stance,
+}
+
+impl ColumnFills {
+    /// Learn fill values from `records`, which callers must have already restricted to rows
+    /// that will actually survive `clean_record`'s validity check (non-positive weight/height).
+    /// A row that's going to be dropped anyway shouldn't be allowed to drag every column's
+    /// mean/median/modal-stance fill away from the rows that remain.
+    fn learn(records: &[&FighterRecord], strategy: ImputationStrategy) -> Self {
+        ColumnFills {
+            weight: column_fill_value(records, |r| r.weight_in_kg, strategy),
+            height: column_fill_value(records, |r| r.height_cm, strategy),
+            reach:  column_fill_value(records, |r| r.reach_in_cm, strategy),
+            s_lpm:  column_fill_value(records, |r| r.significant_strikes_landed_per_minute, strategy),
+            s_abs:  column_fill_value(records, |r| r.significant_strikes_absorbed_per_minute, strategy),
+            tkl15:  column_fill_value(records, |r| r.average_takedowns_landed_per_15_minutes, strategy),
+            sub15:  column_fill_value(records, |r| r.average_submissions_attempted_per_15_minutes, strategy),
+            td_acc: column_fill_value(records, |r| r.takedown_accuracy, strategy),
+            td_def: column_fill_value(records, |r| r.takedown_defense, strategy),
+            modal_stance: modal_stance(records),
+        }
+    }
+}
+
+/// Whether a raw row's weight/height rule out the row before imputation is even considered:
+/// `clean_record` drops any row whose *resolved* weight or height is non-positive, and a
+/// present-but-non-positive value passes through `resolve` unchanged (only missing/NaN values get
+/// filled), so such a row is unconditionally dropped regardless of `ImputationStrategy`.
+fn has_invalid_weight_or_height(r: &FighterRecord) -> bool {
+    matches!(r.weight_in_kg, Some(w) if w <= 0.0) || matches!(r.height_cm, Some(h) if h <= 0.0)
+}
+
+/// Clean and engineer features for a single raw record, independently of every other record (so
+/// this can run on either a sequential or a rayon `par_iter` over the raw slice).
+///
+/// input: one raw record, today's date (for age), the fill values learned over the whole batch,
+/// and the imputation strategy controlling whether a missing field is filled in or dropped
+/// output: `Some(CleanRecord)`, or `None` if the record is invalid (non-positive weight/height),
+/// or if it's missing a field and `strategy` is `Drop`
+fn clean_record(r: &FighterRecord, today: NaiveDate, fills: &ColumnFills, strategy: ImputationStrategy) -> Option<CleanRecord> {
+    // 1) resolve numeric inputs, filling in any that are missing or NaN
+    let (weight, imputed_weight) = resolve(r.weight_in_kg, fills.weight);
+    let (height, imputed_height) = resolve(r.height_cm, fills.height);
+    let (reach,  imputed_reach)  = resolve(r.reach_in_cm, fills.reach);
+    // a non-positive weight/height is invalid rather than missing; still drop those
+    if weight <= 0.0 || height <= 0.0 { return None; }
+    let (s_lpm, imputed_s_lpm) = resolve(r.significant_strikes_landed_per_minute, fills.s_lpm);
+    let (s_abs, imputed_s_abs) = resolve(r.significant_strikes_absorbed_per_minute, fills.s_abs);
+    let (tkl15, imputed_tkl15) = resolve(r.average_takedowns_landed_per_15_minutes, fills.tkl15);
+    let (sub15, imputed_sub15) = resolve(r.average_submissions_attempted_per_15_minutes, fills.sub15);
+    let (td_acc, imputed_td_acc) = resolve(r.takedown_accuracy, fills.td_acc);
+    let (td_def, imputed_td_def) = resolve(r.takedown_defense, fills.td_def);
+
+    // 2) parse stance, falling back to the modal stance under Mean/Median/Constant instead of
+    // dropping the row; under Drop, an unrecognized/missing stance still drops it
+    let (stance, imputed_stance) = match r.stance.parse::<Stance>() {
+        Ok(s) => (s, false),
+        Err(_) if matches!(strategy, ImputationStrategy::Drop) => return None,
+        Err(_) => (fills.modal_stance, true),
+    };
+
+    let is_imputed = if [
+        imputed_weight, imputed_height, imputed_reach,
+        imputed_s_lpm, imputed_s_abs, imputed_tkl15, imputed_sub15,
+        imputed_td_acc, imputed_td_def, imputed_stance,
+    ].iter().any(|&b| b) { 1.0 } else { 0.0 };
+
+    // under Drop, any field that needed filling in means the whole row is dropped
+    if matches!(strategy, ImputationStrategy::Drop) && is_imputed == 1.0 {
+        return None;
+    }
+
+    let (iso, isp, iss) = match stance {
+        Stance::Orthodox => (1.0, 0.0, 0.0),
+        Stance::Southpaw => (0.0, 1.0, 0.0),
+        Stance::Switch   => (0.0, 0.0, 1.0),
+    };
+
+    // 3) compute win rate
+    let total = (r.wins + r.losses + r.draws) as f32;
+    let win_rate = if total > 0.0 { r.wins as f32 / total } else { 0.0 };
+
+    // compute age
+    let mut age = today.year() - r.date_of_birth.year();
+    if (today.month(), today.day()) < (r.date_of_birth.month(), r.date_of_birth.day()) {
+        age -= 1;
+    }
+    let age = age as f32;
+
+    // 4) engineer features
+    let weight_height_ratio = weight / height;
+    let reach_height_ratio  = reach / height;
+    // convert to per-minute rates
+    let takedown_lpm   = tkl15 / 15.0;
+    let submission_lpm = sub15 / 15.0;
+    // efficiency metric
+    let submission_per_takedown = if takedown_lpm > 0.0 { submission_lpm / takedown_lpm } else { 0.0 };
+    // weight class bucket
+    let weight_class = if weight < 56.7 {
+        WeightClass::Flyweight
+    } else if weight < 61.2 {
+        WeightClass::Bantamweight
+    } else if weight < 65.8 {
+        WeightClass::Featherweight
+    } else if weight < 70.3 {
+        WeightClass::Lightweight
+    } else if weight < 77.1 {
+        WeightClass::Welterweight
+    } else if weight < 83.9 {
+        WeightClass::Middleweight
+    } else if weight < 93.0 {
+        WeightClass::LightHeavyweight
+    } else {
+        WeightClass::Heavyweight
+    };
+
+    Some(CleanRecord {
+        stance,
+        is_orthodox:             iso,
+        is_southpaw:             isp,
+        is_switch:               iss,
+        weight_height_ratio,
+        reach_height_ratio,
+        submission_per_takedown,
+        weight_class,
+        age,
+        significant_strikes_lpm: s_lpm,
+        strike_diff:             s_lpm - s_abs,
+        takedown_lpm,
+        submission_lpm,
+        takedown_accuracy:       td_acc,
+        takedown_defense:        td_def,
+        win_rate,
+        is_imputed,
+    })
+}
+
+/// How an engineered numeric feature is rescaled before modeling.
+#[derive(Debug, Clone, Copy)]
+pub enum NormalizationStrategy {
+    /// `(v - min) / (max - min)`, scaling into `[0,1]`.
+    MinMax,
+    /// `(v - mean) / std`, standardizing to zero mean and unit variance.
+    ZScore,
+    /// `(v - median) / IQR` (IQR = Q3 - Q1), robust to outliers since it ignores the extremes.
+    Robust,
+}
+
+/// The `(center, spread)` a feature was normalized against: `min`/`max - min` for `MinMax`,
+/// `mean`/`std` for `ZScore`, `median`/`IQR` for `Robust`.
+#[derive(Debug, Clone, Copy)]
+pub struct FeatureScale {
+    center: f32,
+    spread: f32,
+}
+
+/// Compute the global min/max of one feature across `records`.
+///
+/// With the `parallel` cargo feature enabled this is a parallel reduction; otherwise it's a
+/// single-threaded fold. Either way the result is identical.
+fn feature_min_max(records: &[CleanRecord], getter: impl Fn(&CleanRecord) -> f32 + Sync) -> (f32, f32) {
+    #[cfg(feature = "parallel")]
+    let (min, max) = records.par_iter()
+        .map(&getter)
+        .fold(
+            || (f32::INFINITY, f32::NEG_INFINITY),
+            |(mi, ma), v| (mi.min(v), ma.max(v)),
+        )
+        .reduce(
+            || (f32::INFINITY, f32::NEG_INFINITY),
+            |a, b| (a.0.min(b.0), a.1.max(b.1)),
         );
-        let range = max - min;
-
-         // 2) Normalize each record in place
-        for rec in records.iter_mut() {
-            let v = getter(rec);
-            // avoid division by zero when all values are equal
-            let norm = if range > 0.0 { (v - min) / range } else { 0.0 };
-            setter(rec, norm);
+    #[cfg(not(feature = "parallel"))]
+    let (min, max) = records.iter().fold(
+        (f32::INFINITY, f32::NEG_INFINITY),
+        |(mi, ma), r| {
+            let v = getter(r);
+            (mi.min(v), ma.max(v))
+        },
+    );
+    (min, max)
+}
+
+/// Mean and (population) standard deviation of one feature across `records`, computed in a
+/// single pass over the values.
+fn feature_mean_std(records: &[CleanRecord], getter: impl Fn(&CleanRecord) -> f32) -> (f32, f32) {
+    let n = records.len() as f32;
+    if n == 0.0 {
+        return (0.0, 0.0);
+    }
+    let mean = records.iter().map(&getter).sum::<f32>() / n;
+    let variance = records.iter().map(|r| {
+        let d = getter(r) - mean;
+        d * d
+    }).sum::<f32>() / n;
+    (mean, variance.sqrt())
+}
+
+/// Linearly-interpolated percentile (`p` in `[0,1]`) of an already-sorted slice.
+fn percentile(sorted: &[f32], p: f32) -> f32 {
+    match sorted.len() {
+        0 => 0.0,
+        1 => sorted[0],
+        len => {
+            let idx = p * (len - 1) as f32;
+            let lo = idx.floor() as usize;
+            let hi = idx.ceil() as usize;
+            if lo == hi {
+                sorted[lo]
+            } else {
+                sorted[lo] + (sorted[hi] - sorted[lo]) * (idx - lo as f32)
+            }
         }
     }
+}
 
-    // Apply normalization to each engineered feature
-    normalize(&mut cleaned, |r| r.weight_height_ratio,      |r,v| r.weight_height_ratio = v);
-    normalize(&mut cleaned, |r| r.reach_height_ratio,       |r,v| r.reach_height_ratio = v);
-    normalize(&mut cleaned, |r| r.submission_per_takedown,  |r,v| r.submission_per_takedown = v);
-    normalize(&mut cleaned, |r| r.age,                      |r,v| r.age = v);
-    normalize(&mut cleaned, |r| r.significant_strikes_lpm,  |r,v| r.significant_strikes_lpm = v);
-    normalize(&mut cleaned, |r| r.strike_diff,              |r,v| r.strike_diff = v);
-    normalize(&mut cleaned, |r| r.takedown_lpm,             |r,v| r.takedown_lpm = v);
-    normalize(&mut cleaned, |r| r.submission_lpm,           |r,v| r.submission_lpm = v);
-    normalize(&mut cleaned, |r| r.takedown_accuracy,        |r,v| r.takedown_accuracy = v);
-    normalize(&mut cleaned, |r| r.takedown_defense,         |r,v| r.takedown_defense = v);
-    normalize(&mut cleaned, |r| r.win_rate,                 |r,v| r.win_rate = v);
+/// Median and interquartile range (Q3 - Q1) of one feature across `records`.
+fn feature_median_iqr(records: &[CleanRecord], getter: impl Fn(&CleanRecord) -> f32) -> (f32, f32) {
+    let mut values: Vec<f32> = records.iter().map(&getter).collect();
+    values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let median = percentile(&values, 0.5);
+    let iqr = percentile(&values, 0.75) - percentile(&values, 0.25);
+    (median, iqr)
+}
+
+/// Fit a feature's `(center, spread)` over `records` under the given `strategy`.
+fn feature_scale(
+    records: &[CleanRecord],
+    getter: impl Fn(&CleanRecord) -> f32 + Sync,
+    strategy: NormalizationStrategy,
+) -> FeatureScale {
+    let (center, spread) = match strategy {
+        NormalizationStrategy::MinMax => {
+            let (min, max) = feature_min_max(records, &getter);
+            (min, max - min)
+        }
+        NormalizationStrategy::ZScore => feature_mean_std(records, &getter),
+        NormalizationStrategy::Robust => feature_median_iqr(records, &getter),
+    };
+    FeatureScale { center, spread }
+}
+
+/// Normalize one feature of every record in place, using the given `scale` rather than
+/// recomputing it. Falls back to 0.0 when `scale.spread == 0`; when `clamp` is set (used when
+/// reapplying a previously-fit min-max `scale` to unseen records) the result is clamped to
+/// `[0,1]` so out-of-range inputs don't escape the training distribution.
+fn apply_scale(
+    records: &mut [CleanRecord],
+    scale: FeatureScale,
+    getter: impl Fn(&CleanRecord) -> f32 + Sync,
+    setter: impl Fn(&mut CleanRecord, f32) + Sync,
+    clamp: bool,
+) {
+    let transform = |v: f32| -> f32 {
+        let norm = if scale.spread > 0.0 { (v - scale.center) / scale.spread } else { 0.0 };
+        if clamp { norm.clamp(0.0, 1.0) } else { norm }
+    };
+
+    #[cfg(feature = "parallel")]
+    records.par_iter_mut().for_each(|rec| setter(rec, transform(getter(rec))));
+    #[cfg(not(feature = "parallel"))]
+    for rec in records.iter_mut() {
+        setter(rec, transform(getter(rec)));
+    }
+}
+
+/// Fit a feature's scale over `records` under `strategy` and normalize it in place, returning
+/// the fitted scale so it can be stored in a `FeatureScaler` for later reuse on unseen records.
+fn fit_and_apply(
+    records: &mut [CleanRecord],
+    getter: impl Fn(&CleanRecord) -> f32 + Sync,
+    setter: impl Fn(&mut CleanRecord, f32) + Sync,
+    strategy: NormalizationStrategy,
+) -> FeatureScale {
+    let scale = feature_scale(records, &getter, strategy);
+    apply_scale(records, scale, &getter, &setter, false);
+    scale
+}
 
-    cleaned
+/// The per-feature scales learned by `preprocess_with_scaler`, so the exact same normalization
+/// can later be reapplied to freshly cleaned, unseen records (e.g. to score one new fighter
+/// against a model trained on an earlier batch) instead of normalizing them against their own,
+/// unrelated distribution.
+// Not currently reapplied by `main` (which only ever processes one batch per run); exercised
+// directly by its own test in the meantime.
+#[allow(dead_code)]
+#[derive(Debug, Clone)]
+pub struct FeatureScaler {
+    strategy:                NormalizationStrategy,
+    weight_height_ratio:     FeatureScale,
+    reach_height_ratio:      FeatureScale,
+    submission_per_takedown: FeatureScale,
+    age:                     FeatureScale,
+    significant_strikes_lpm: FeatureScale,
+    strike_diff:             FeatureScale,
+    takedown_lpm:            FeatureScale,
+    submission_lpm:          FeatureScale,
+    takedown_accuracy:       FeatureScale,
+    takedown_defense:        FeatureScale,
+    win_rate:                FeatureScale,
+}
+
+impl FeatureScaler {
+    /// Apply the fitted scales to `records` in place. Under `MinMax` the result is clamped to
+    /// `[0,1]` so values outside the training range don't escape it; `ZScore` and `Robust`
+    /// aren't bounded to begin with, so unseen records are left unclamped.
+    #[allow(dead_code)]
+    pub fn transform(&self, records: &mut [CleanRecord]) {
+        let clamp = matches!(self.strategy, NormalizationStrategy::MinMax);
+        apply_scale(records, self.weight_height_ratio,     |r| r.weight_height_ratio,     |r,v| r.weight_height_ratio = v,     clamp);
+        apply_scale(records, self.reach_height_ratio,      |r| r.reach_height_ratio,      |r,v| r.reach_height_ratio = v,      clamp);
+        apply_scale(records, self.submission_per_takedown, |r| r.submission_per_takedown, |r,v| r.submission_per_takedown = v, clamp);
+        apply_scale(records, self.age,                     |r| r.age,                     |r,v| r.age = v,                     clamp);
+        apply_scale(records, self.significant_strikes_lpm, |r| r.significant_strikes_lpm, |r,v| r.significant_strikes_lpm = v, clamp);
+        apply_scale(records, self.strike_diff,             |r| r.strike_diff,             |r,v| r.strike_diff = v,             clamp);
+        apply_scale(records, self.takedown_lpm,            |r| r.takedown_lpm,            |r,v| r.takedown_lpm = v,            clamp);
+        apply_scale(records, self.submission_lpm,          |r| r.submission_lpm,          |r,v| r.submission_lpm = v,          clamp);
+        apply_scale(records, self.takedown_accuracy,       |r| r.takedown_accuracy,       |r,v| r.takedown_accuracy = v,       clamp);
+        apply_scale(records, self.takedown_defense,        |r| r.takedown_defense,        |r,v| r.takedown_defense = v,        clamp);
+        apply_scale(records, self.win_rate,                |r| r.win_rate,                |r,v| r.win_rate = v,                clamp);
+    }
+}
+
+/// Clean raw fighter records, engineer normalized features for modeling, and return the fitted
+/// `FeatureScaler` alongside them.
+/// input: raw, CSV‑deserialized data, the `ImputationStrategy` used to fill missing numerics,
+/// and the `NormalizationStrategy` used to rescale every engineered feature
+/// output: valid records with one‑hot flags, ratios, rates, an imputation flag, and normalized
+/// numerics, plus the per-feature scales the normalization was fit on
+/// logic: fill missing/NaN numeric fields via `imputation` instead of dropping the row;
+/// one‑hot encode stance; compute win_rate, age, weight/height & reach/height ratios, per‑minute
+/// rates, efficiency, weight_class; fit and apply `normalization` to all numeric features
+///
+/// With the `parallel` cargo feature enabled, per-record cleaning runs over a rayon
+/// `par_iter` and the `MinMax` min/max reduction uses `par_iter` too, with every normalization
+/// pass applied via `par_iter_mut`; without it, everything runs on a single thread. Either way
+/// the numeric output is identical, since every record is cleaned independently of every other.
+pub fn preprocess_with_scaler(
+    records: &[FighterRecord],
+    imputation: ImputationStrategy,
+    normalization: NormalizationStrategy,
+) -> (Vec<CleanRecord>, FeatureScaler) {
+    let today = Local::now().date_naive();
+    // Learn fills only from rows that'll survive cleaning, so a row that's dropped outright for
+    // having a non-positive weight/height can't drag every column's fill away from the rest.
+    let fillable: Vec<&FighterRecord> = records.iter()
+        .filter(|r| !has_invalid_weight_or_height(r))
+        .collect();
+    let fills = ColumnFills::learn(&fillable, imputation);
+
+    #[cfg(feature = "parallel")]
+    let mut cleaned: Vec<CleanRecord> = records.par_iter()
+        .filter_map(|r| clean_record(r, today, &fills, imputation))
+        .collect();
+    #[cfg(not(feature = "parallel"))]
+    let mut cleaned: Vec<CleanRecord> = records.iter()
+        .filter_map(|r| clean_record(r, today, &fills, imputation))
+        .collect();
+
+    let scaler = FeatureScaler {
+        strategy:                normalization,
+        weight_height_ratio:     fit_and_apply(&mut cleaned, |r| r.weight_height_ratio,     |r,v| r.weight_height_ratio = v,     normalization),
+        reach_height_ratio:      fit_and_apply(&mut cleaned, |r| r.reach_height_ratio,      |r,v| r.reach_height_ratio = v,      normalization),
+        submission_per_takedown: fit_and_apply(&mut cleaned, |r| r.submission_per_takedown, |r,v| r.submission_per_takedown = v, normalization),
+        age:                     fit_and_apply(&mut cleaned, |r| r.age,                     |r,v| r.age = v,                     normalization),
+        significant_strikes_lpm: fit_and_apply(&mut cleaned, |r| r.significant_strikes_lpm, |r,v| r.significant_strikes_lpm = v, normalization),
+        strike_diff:             fit_and_apply(&mut cleaned, |r| r.strike_diff,             |r,v| r.strike_diff = v,             normalization),
+        takedown_lpm:            fit_and_apply(&mut cleaned, |r| r.takedown_lpm,            |r,v| r.takedown_lpm = v,            normalization),
+        submission_lpm:          fit_and_apply(&mut cleaned, |r| r.submission_lpm,          |r,v| r.submission_lpm = v,          normalization),
+        takedown_accuracy:       fit_and_apply(&mut cleaned, |r| r.takedown_accuracy,       |r,v| r.takedown_accuracy = v,       normalization),
+        takedown_defense:        fit_and_apply(&mut cleaned, |r| r.takedown_defense,        |r,v| r.takedown_defense = v,        normalization),
+        win_rate:                fit_and_apply(&mut cleaned, |r| r.win_rate,                |r,v| r.win_rate = v,                normalization),
+    };
+
+    (cleaned, scaler)
+}
+
+/// Clean raw fighter records and engineer normalized features for modeling, discarding the
+/// fitted `FeatureScaler`. Use `preprocess_with_scaler` directly when the scaler needs to be
+/// reapplied later (e.g. to score new fighters against an already-trained model).
+pub fn preprocess(
+    records: &[FighterRecord],
+    imputation: ImputationStrategy,
+    normalization: NormalizationStrategy,
+) -> Vec<CleanRecord> {
+    preprocess_with_scaler(records, imputation, normalization).0
 }
 
 /// Load raw CSV and run preprocessing to produce cleaned records
-/// input: filesystem path to the fighters CSV
+/// input: filesystem path to the fighters CSV, the imputation strategy for missing numerics,
+/// and the normalization strategy for engineered features
 /// output: cleaned and normalized data or error
-/// logic: call "io::load_csv(path)" to parse raw records, then "preprocess(&raw)" to engineer features
-pub fn make_weight_driven_data(path: &str) -> Result<Vec<CleanRecord>, Box<dyn Error>> {
+/// logic: call "io::load_csv(path)" to parse raw records, then "preprocess(&raw, imputation,
+/// normalization)" to engineer features
+///
+/// Not currently called by `main` (which loads and preprocesses inline to interleave its own
+/// progress messages), but kept as the one-call entry point for scripts/tests that just want
+/// cleaned records from a path.
+#[allow(dead_code)]
+pub fn make_weight_driven_data(
+    path: &str,
+    imputation: ImputationStrategy,
+    normalization: NormalizationStrategy,
+) -> Result<Vec<CleanRecord>, Box<dyn Error>> {
     let raw = crate::io::load_csv(path)?;
-    Ok(preprocess(&raw))
+    Ok(preprocess(&raw, imputation, normalization))
+}
+
+/// Index of a `WeightClass` into a fixed 8-slot frequency table.
+// Only called from `resample_balanced`, which isn't wired into `main` yet either; see its own
+// comment below.
+#[allow(dead_code)]
+fn weight_class_index(class: WeightClass) -> usize {
+    match class {
+        WeightClass::Flyweight => 0,
+        WeightClass::Bantamweight => 1,
+        WeightClass::Featherweight => 2,
+        WeightClass::Lightweight => 3,
+        WeightClass::Welterweight => 4,
+        WeightClass::Middleweight => 5,
+        WeightClass::LightHeavyweight => 6,
+        WeightClass::Heavyweight => 7,
+    }
+}
+
+/// Seed for the alias-method sampler in `resample_balanced`, so a given dataset always produces
+/// the same resampled indices across runs.
+#[allow(dead_code)]
+const RESAMPLE_SEED: u64 = 42;
+
+/// Vose's alias method: given `n` items with non-negative weights, builds `prob`/`alias` tables
+/// that let each subsequent draw pick a weighted-random index in O(1) instead of O(log n).
+#[allow(dead_code)]
+struct AliasTable {
+    prob: Vec<f64>,
+    alias: Vec<usize>,
+}
+
+#[allow(dead_code)]
+impl AliasTable {
+    /// Build the alias table for `weights` (need not sum to 1; any non-negative weights work,
+    /// so long as at least one is positive).
+    fn new(weights: &[f64]) -> Self {
+        let n = weights.len();
+        let total: f64 = weights.iter().sum();
+        let mut scaled: Vec<f64> = weights.iter().map(|&w| n as f64 * w / total).collect();
+
+        let mut small: Vec<usize> = Vec::new();
+        let mut large: Vec<usize> = Vec::new();
+        for (i, &p) in scaled.iter().enumerate() {
+            if p < 1.0 { small.push(i) } else { large.push(i) }
+        }
+
+        let mut prob = vec![0.0; n];
+        let mut alias = vec![0usize; n];
+
+        while !small.is_empty() && !large.is_empty() {
+            let l = small.pop().unwrap();
+            let g = large.pop().unwrap();
+            prob[l] = scaled[l];
+            alias[l] = g;
+            scaled[g] = (scaled[g] + scaled[l]) - 1.0;
+            if scaled[g] < 1.0 { small.push(g) } else { large.push(g) }
+        }
+        // Leftover indices only missed their partner due to floating-point rounding; both sides
+        // represent a fully-weighted slot.
+        for i in large.into_iter().chain(small) {
+            prob[i] = 1.0;
+        }
+
+        AliasTable { prob, alias }
+    }
+
+    /// Draw one weighted-random index in O(1).
+    fn sample(&self, rng: &mut StdRng) -> usize {
+        let i = rng.gen_range(0..self.prob.len());
+        if rng.gen::<f64>() < self.prob[i] { i } else { self.alias[i] }
+    }
+}
+
+/// Draw `target_n` indices into `records`, oversampling fighters from minority `WeightClass`
+/// buckets so the result is balanced across classes, using Vose's alias method for O(1) draws.
+///
+/// input: cleaned records and the desired number of sampled indices
+/// output: `target_n` indices into `records` (with repeats), drawn with per-record weight
+/// inversely proportional to that record's weight-class frequency
+/// logic: build Vose's alias table and sample it, this lets callers build a class-balanced
+/// training slice (e.g. `idx.iter().map(|&i| &records[i])`) from a skewed input dataset
+///
+/// Not currently wired into `main`'s CLI (no flag yet opts a run into resampled, class-balanced
+/// training); exercised directly by its own test in the meantime.
+#[allow(dead_code)]
+pub fn resample_balanced(records: &[CleanRecord], target_n: usize) -> Vec<usize> {
+    if records.is_empty() || target_n == 0 {
+        return Vec::new();
+    }
+
+    let mut class_counts = [0usize; 8];
+    for r in records {
+        class_counts[weight_class_index(r.weight_class)] += 1;
+    }
+
+    let weights: Vec<f64> = records.iter()
+        .map(|r| 1.0 / class_counts[weight_class_index(r.weight_class)] as f64)
+        .collect();
+
+    let table = AliasTable::new(&weights);
+    let mut rng = StdRng::seed_from_u64(RESAMPLE_SEED);
+    (0..target_n).map(|_| table.sample(&mut rng)).collect()
 }